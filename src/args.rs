@@ -24,6 +24,63 @@ pub struct Args {
     /// path of the config ref mapping dir
     #[arg(default_value_t = String::from("ConfigRefNameMapping/"), long)]
     pub ref_mapping_dir: String,
+    /// path of the output data dir
+    #[arg(default_value_t = String::from("ExportData/"), long)]
+    pub output_data_dir: String,
+    /// output dir of the .proto files
+    #[arg(default_value_t = String::from("ExportProto/"), long)]
+    pub output_proto_dir: String,
+    /// output dir of the lua modules
+    #[arg(default_value_t = String::from("ExportLua/"), long)]
+    pub output_lua_dir: String,
+    /// output dir of the typescript modules
+    #[arg(default_value_t = String::from("ExportTs/"), long)]
+    pub output_ts_dir: String,
+    /// output dir of the c++ headers
+    #[arg(default_value_t = String::from("ExportCpp/"), long)]
+    pub output_cpp_dir: String,
+    /// output dir of the rust modules
+    #[arg(default_value_t = String::from("ExportRs/"), long)]
+    pub output_rs_dir: String,
+    /// output dir of the docs pages
+    #[arg(default_value_t = String::from("ExportDocs/"), long)]
+    pub output_docs_dir: String,
+    /// output dir of the foreign key graph
+    #[arg(default_value_t = String::from("ExportGraph/"), long)]
+    pub output_graph_dir: String,
+    /// output dir of the unity assets and their scripts
+    #[arg(default_value_t = String::from("ExportUnity/"), long)]
+    pub output_unity_dir: String,
+    /// what to export, separated by ',': cs, json, bin, msgpack, proto, sqlite, lua, ts, cpp, rs, schema, docs, graph, unity. cs with json or bin loads the rows from that file
+    #[arg(default_value_t = String::from("cs"), long)]
+    pub export: String,
+    /// generate helper classes of enums or not
+    #[arg(default_value_t = false, long)]
+    pub enum_helpers: bool,
+    /// generate [MessagePackObject] classes of the msgpack rows or not
+    #[arg(default_value_t = false, long)]
+    pub msgpack_classes: bool,
+    /// write the ts rows as .json with .d.ts declarations instead of .ts
+    #[arg(default_value_t = false, long)]
+    pub ts_json: bool,
+    /// C# type of the list and array columns: mutable (List<T>, T[]), readonly (IReadOnlyList<T>) or immutable (ImmutableArray<T>)
+    #[arg(default_value_t = String::from("mutable"), long)]
+    pub cs_collections: String,
+    /// write the cell literals which rows share once, as static fields of the table class. only tuples, and lists and arrays unless --cs-collections is mutable. no effect when cs loads its rows from json or bin
+    #[arg(default_value_t = false, long)]
+    pub pool_literals: bool,
+    /// dir of C# templates replacing the built-in ones of the same name, empty uses only the built-in ones
+    #[arg(default_value_t = String::from(""), long)]
+    pub template_dir: String,
+    /// tables exported as unity assets, separated by ',', empty for all of them
+    #[arg(default_value_t = String::from(""), long)]
+    pub unity_tables: String,
+    /// json of the namespace, usings, class suffixes and enum naming of the generated C#, overridable per workbook. empty keeps namespace Config
+    #[arg(default_value_t = String::from(""), long)]
+    pub project_config: String,
+    /// name of the shared enum library xlsx
+    #[arg(default_value_t = String::from("EnumLibrary"), long)]
+    pub enum_library: String,
     #[command(subcommand)]
     pub command: Command,
 }
@@ -33,6 +90,38 @@ pub enum Command {
     #[command(name = "build", visible_alias = "b")]
     Build,
 
+    /// delete the outputs, the enum histories (*.enum.txt) are kept
     #[command(name = "clean", visible_alias = "c")]
     Clean,
+
+    /// dump the Template and t_ sheets as text next to the xlsx, for diffs. only the -n workbook when given
+    #[command(name = "dump", visible_alias = "d")]
+    Dump {
+        /// csv or tsv
+        #[arg(default_value_t = String::from("tsv"), long)]
+        format: String,
+    },
+
+    /// write the built-in C# templates into --template-dir, to start from
+    #[command(name = "templates", visible_alias = "t")]
+    Templates,
+
+    /// write a page per table and enum into --output-docs-dir, nothing else is exported
+    #[command(name = "docs", visible_alias = "doc")]
+    Docs {
+        /// md or html
+        #[arg(default_value_t = String::from("md"), long)]
+        format: String,
+    },
+
+    /// write the foreign keys between the tables as FkGraph.dot and FkGraph.json into --output-graph-dir
+    #[command(name = "graph", visible_alias = "g")]
+    Graph {
+        /// only the tables around this one, empty for all of them
+        #[arg(default_value_t = String::from(""), long)]
+        focus: String,
+        /// how many foreign keys away from --focus, in either direction
+        #[arg(default_value_t = 1, long)]
+        depth: usize,
+    },
 }
\ No newline at end of file
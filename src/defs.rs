@@ -23,6 +23,28 @@ pub static mut OUTPUT_SCRIPT_CODE_DIR: &'static str = "ExportScripts/";
 pub static mut OUTPUT_ENUM_CODE_DIR: &'static str = "ConfigExportEnum/";
 pub static mut SOURCE_XLSXS_DIR: &'static str = "D:/Config-beta/";
 pub static mut REF_TEXT_DIR: &'static str = "ConfigRefNameMapping/";
+pub static mut OUTPUT_DATA_DIR: &'static str = "ExportData/";
+pub static mut OUTPUT_PROTO_DIR: &'static str = "ExportProto/";
+pub static mut OUTPUT_LUA_DIR: &'static str = "ExportLua/";
+pub static mut OUTPUT_TS_DIR: &'static str = "ExportTs/";
+pub static mut OUTPUT_CPP_DIR: &'static str = "ExportCpp/";
+pub static mut OUTPUT_RS_DIR: &'static str = "ExportRs/";
+pub static mut OUTPUT_DOCS_DIR: &'static str = "ExportDocs/";
+pub static mut OUTPUT_GRAPH_DIR: &'static str = "ExportGraph/";
+pub static mut OUTPUT_UNITY_DIR: &'static str = "ExportUnity/";
+pub static mut EXPORT_FORMATS: &'static str = "cs";
+pub static mut ENUM_LIBRARY_NAME: &'static str = "EnumLibrary";
+pub static mut GEN_ENUM_HELPERS: bool = false;
+pub static mut GEN_MSGPACK_CLASSES: bool = false;
+pub static mut GEN_TS_JSON: bool = false;
+pub static mut CS_TEMPLATE_DIR: &'static str = "";
+pub static mut DOCS_FORMAT: &'static str = "md";
+pub static mut GRAPH_FOCUS: &'static str = "";
+pub static mut GRAPH_DEPTH: usize = 1;
+pub static mut UNITY_TABLES: &'static str = "";
+pub static mut PROJECT_CONFIG: &'static str = "";
+pub static mut CS_COLLECTIONS: &'static str = "mutable";
+pub static mut POOL_LITERALS: bool = false;
 
 // 默认多少行数据切换构造方法
 pub const DEFAULT_LINES: usize = 101;
@@ -31,11 +53,17 @@ pub const DEFAULT_LINES: usize = 101;
 pub const DEFAULT_SOURCE_SUFFIX: &'static str = "xlsx";
 pub const DEFAULT_DEST_SUFFIX: &'static str = "cs";
 pub const DEFAULT_DEF_SUFFIX: &'static str = "ref.txt";
+// 枚举成员及废弃值的记录文件后缀, 与导出格式无关
+pub const DEFAULT_ENUM_HISTORY_SUFFIX: &'static str = "enum.txt";
+// sqlite导出的数据库文件名
+pub const SQLITE_DB_NAME: &'static str = "Config.sqlite";
 
 // enum列属性
 pub const ENUM_COL_IDENT: usize = 0;
 pub const ENUM_COL_VAL: usize = 1;
 pub const ENUM_COL_DESC: usize = 2;
+// 已废弃枚举值的记录行
+pub const ENUM_RESERVED_FLAG: &'static str = "// Reserved: ";
 
 // 行结束符
 pub const LINE_END_FLAG: &'static str = "\r\n";
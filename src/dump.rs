@@ -0,0 +1,139 @@
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, Result, Write};
+use std::path::Path;
+
+use xlsx_read::{excel_file::ExcelFile, excel_table::ExcelTable};
+
+use crate::defs::{DEFAULT_SOURCE_SUFFIX, DATA_IDENTIFY_ROW, LINE_END_FLAG};
+use crate::parser::find_file;
+
+const SKIP_COL_FLAG: &'static str = "#skip";
+
+// {Base}.Template.tsv and {Base}.t_{Name}.tsv next to every xlsx, rows after EOF are cut off.
+// the first line of a Template dump marks the skip columns
+pub fn dump_dir<P: AsRef<Path>>(dir: P, ext: &str) -> Result<()> {
+    let sep = separator(ext)?;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let file_name = path.file_name().and_then(|v| v.to_str()).unwrap_or_default().to_string();
+
+        if path.is_dir() && !file_name.starts_with('.') {
+            dump_dir(&path, ext)?;
+        } else if path.extension().is_some_and(|x| x == DEFAULT_SOURCE_SUFFIX) && !file_name.starts_with('~') {
+            let base_name = &file_name[..file_name.find('.').unwrap_or(file_name.len())];
+            dump_file(&path, base_name, ext, sep)?;
+        }
+    }
+    Ok(())
+}
+
+// only {name}.xlsx, found anywhere under dir like the build does
+pub fn dump_name<P: AsRef<Path>>(dir: P, name: &str, ext: &str) -> Result<()> {
+    let sep = separator(ext)?;
+    let path = find_file(dir, &format!("{}.{}", name, DEFAULT_SOURCE_SUFFIX));
+    if !path.is_file() {
+        return Err(Error::new(ErrorKind::NotFound, format!("cant find {}.{}", name, DEFAULT_SOURCE_SUFFIX)));
+    }
+    dump_file(&path, name, ext, sep)
+}
+
+fn separator(ext: &str) -> Result<char> {
+    match ext {
+        "tsv" => Ok('\t'),
+        "csv" => Ok(','),
+        _ => Err(Error::new(ErrorKind::InvalidInput, format!("unknown dump format: {}, expected csv or tsv", ext))),
+    }
+}
+
+fn dump_file(path: &Path, base_name: &str, ext: &str, sep: char) -> Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut ff = ExcelFile::load_from_path(path).map_err(|e| Error::new(ErrorKind::Other, e))?;
+    let sheets = ff.parse_workbook().map_err(|e| Error::new(ErrorKind::Other, e))?;
+
+    // dumps of removed sheets must not stay behind
+    let prefix = format!("{}.", base_name);
+    let suffix = format!(".{}", ext);
+    for entry in fs::read_dir(dir)? {
+        let old = entry?.path();
+        if old.file_name().and_then(|v| v.to_str()).is_some_and(|v| v.starts_with(&prefix) && v.ends_with(&suffix)) {
+            fs::remove_file(old)?;
+        }
+    }
+
+    let mut dumped = false;
+    for (name, id) in sheets.iter() {
+        if name != "Template" && !name.starts_with("t_") {
+            continue;
+        }
+        if let Ok(table) = ff.parse_sheet(*id) {
+            let dest = dir.join(format!("{}.{}.{}", base_name, name, ext));
+            let mut file = File::create(dest)?;
+            dump_table(&table, name == "Template", sep, &mut file)?;
+            dumped = true;
+        }
+    }
+    if dumped {
+        println!("Dump file_name: {}.{}", base_name, DEFAULT_SOURCE_SUFFIX);
+    }
+    Ok(())
+}
+
+fn dump_table<W: Write + ?Sized>(table: &ExcelTable, is_template: bool, sep: char, stream: &mut W) -> Result<()> {
+    // same cut-off as the parser
+    let mut height = table.height();
+    for row in 0..height {
+        if table.cell(0, row).is_some_and(|v| v.contains("EOF")) {
+            height = row;
+            break;
+        }
+    }
+    // trailing empty rows and columns are not part of the content
+    while height > 0 && (0..table.width()).all(|col| table.cell(col, height - 1).map_or(true, |v| v.is_empty())) {
+        height -= 1;
+    }
+    let width = (0..table.width()).rev()
+        .find(|col| (0..height).any(|row| table.cell(*col, row).is_some_and(|v| !v.is_empty())))
+        .map_or(0, |v| v + 1);
+
+    if is_template {
+        let marks = (0..width).map(|col| {
+            match table.cell(col, DATA_IDENTIFY_ROW) {
+                Some(v) if !v.starts_with('#') => "",
+                _ => SKIP_COL_FLAG,
+            }
+        }).collect::<Vec<&str>>();
+        stream.write_fmt(format_args!("{}{}", marks.join(&sep.to_string()), LINE_END_FLAG))?;
+    }
+
+    for row in 0..height {
+        let cells = (0..width).map(|col| {
+            escape(table.cell(col, row).map_or("", |v| v.as_str()), sep)
+        }).collect::<Vec<String>>();
+        stream.write_fmt(format_args!("{}{}", cells.join(&sep.to_string()), LINE_END_FLAG))?;
+    }
+    stream.flush()
+}
+
+// tsv escapes the control characters to keep one row per line,
+// csv quotes as RFC 4180 and keeps the line breaks of the cell inside the quotes
+fn escape(v: &str, sep: char) -> String {
+    if sep == '\t' {
+        v.replace('\\', "\\\\").replace('\t', "\\t").replace('\r', "\\r").replace('\n', "\\n")
+    } else if v.contains(|c| c == sep || c == '"' || c == '\r' || c == '\n') {
+        format!("\"{}\"", v.replace('"', "\"\""))
+    } else {
+        String::from(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape;
+
+    #[test]
+    fn csv_cells_keep_their_line_breaks() {
+        assert_eq!(escape("a,\"b\"", ','), "\"a,\"\"b\"\"\"");
+        assert_eq!(escape("a\r\nb", ','), "\"a\r\nb\"");
+        assert_eq!(escape("a\r\nb\t\\", '\t'), "a\\r\\nb\\t\\\\");
+    }
+}
@@ -0,0 +1,154 @@
+use crate::defs::{EXPORT_FORMATS, POOL_LITERALS};
+use crate::parser::{TableModel, EnumDef, CellValue};
+use std::io::{Error, ErrorKind, Result};
+
+pub use csharp::{CSharpEmitter, DataSource};
+pub mod csharp;
+
+pub use json::JsonEmitter;
+pub mod json;
+
+pub use binary::BinaryEmitter;
+pub mod binary;
+
+pub use msgpack::MsgPackEmitter;
+pub mod msgpack;
+
+pub use proto::ProtoEmitter;
+pub mod proto;
+
+pub use sqlite::SqliteEmitter;
+pub mod sqlite;
+
+pub use lua::LuaEmitter;
+pub mod lua;
+
+pub use typescript::TsEmitter;
+pub mod typescript;
+
+pub use cpp::CppEmitter;
+pub mod cpp;
+
+pub use rust::RustEmitter;
+pub mod rust;
+
+pub use schema::SchemaEmitter;
+pub mod schema;
+
+pub use docs::DocsEmitter;
+pub mod docs;
+
+pub use graph::GraphEmitter;
+pub mod graph;
+
+pub use unity::UnityEmitter;
+pub mod unity;
+
+const FORMATS: [&str; 14] = ["cs", "json", "bin", "msgpack", "proto", "sqlite", "lua", "ts", "cpp", "rs", "schema", "docs", "graph", "unity"];
+
+// one output target, parsing never depends on what is emitted
+pub trait Emitter {
+    // everything of one workbook
+    fn emit(&self, model: &TableModel) -> Result<()>;
+    // enums owned by no workbook: the enum library and the enumflags referenced types
+    fn emit_enum(&self, _def: &EnumDef) -> Result<()> {
+        Ok(())
+    }
+    // once after all the workbooks
+    fn finish(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub fn check_formats() -> Result<()> {
+    let formats = formats();
+    for v in formats.iter() {
+        if !FORMATS.contains(v) {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("unknown export format: {}, expected one of {}", v, FORMATS.join(", "))));
+        }
+    }
+    if formats.contains(&"cs") && formats.contains(&"json") && formats.contains(&"bin") {
+        return Err(Error::new(ErrorKind::InvalidInput, "cs loads its rows from one data format, export either json or bin with it"));
+    }
+    // the pools are fields of the rows baked into the C# code
+    if unsafe { POOL_LITERALS } && (!formats.contains(&"cs") || formats.contains(&"json") || formats.contains(&"bin")) {
+        println!("--pool-literals has no effect, only cs without json or bin bakes the rows into the code");
+    }
+    Ok(())
+}
+
+pub fn emitters() -> Vec<Box<dyn Emitter>> {
+    let formats = formats();
+    let mut ret = Vec::<Box<dyn Emitter>>::default();
+
+    if formats.contains(&"cs") {
+        // the classes read the exported rows instead of baking them
+        let data = if formats.contains(&"json") {
+            DataSource::Json
+        } else if formats.contains(&"bin") {
+            DataSource::Binary
+        } else {
+            DataSource::Code
+        };
+        ret.push(Box::new(CSharpEmitter { data }));
+    }
+    if formats.contains(&"json") {
+        ret.push(Box::new(JsonEmitter));
+    }
+    if formats.contains(&"bin") {
+        ret.push(Box::new(BinaryEmitter));
+    }
+    if formats.contains(&"msgpack") {
+        ret.push(Box::new(MsgPackEmitter));
+    }
+    if formats.contains(&"proto") {
+        ret.push(Box::new(ProtoEmitter));
+    }
+    if formats.contains(&"sqlite") {
+        ret.push(Box::new(SqliteEmitter));
+    }
+    if formats.contains(&"lua") {
+        ret.push(Box::new(LuaEmitter));
+    }
+    if formats.contains(&"ts") {
+        ret.push(Box::new(TsEmitter));
+    }
+    if formats.contains(&"cpp") {
+        ret.push(Box::new(CppEmitter));
+    }
+    if formats.contains(&"rs") {
+        ret.push(Box::new(RustEmitter));
+    }
+    if formats.contains(&"schema") {
+        ret.push(Box::new(SchemaEmitter));
+    }
+    if formats.contains(&"docs") {
+        ret.push(Box::new(DocsEmitter));
+    }
+    if formats.contains(&"graph") {
+        ret.push(Box::new(GraphEmitter));
+    }
+    if formats.contains(&"unity") {
+        ret.push(Box::new(UnityEmitter));
+    }
+    ret
+}
+
+pub fn enabled(format: &str) -> bool {
+    formats().contains(&format)
+}
+
+// C# literal of an empty cell of the type, None for the reference types which every format writes as its null
+pub fn empty_literal(ty: &std::rc::Rc<String>) -> Option<&'static str> {
+    match CellValue::get_type(ty) {
+        CellValue::DBool(_) => Some("false"),
+        CellValue::DSByte(_) | CellValue::DLString(_) | CellValue::DInt(_) | CellValue::DShort(_) => Some("-1"),
+        CellValue::DDouble(_) | CellValue::DFloat(_) => Some("0.0"),
+        CellValue::DUInt(_) | CellValue::DByte(_) | CellValue::DUShort(_) => Some("0"),
+        _ => None,
+    }
+}
+
+fn formats() -> Vec<&'static str> {
+    unsafe { EXPORT_FORMATS }.split(',').map(|v| v.trim()).filter(|v| !v.is_empty()).collect()
+}
@@ -0,0 +1,338 @@
+use crate::defs::OUTPUT_DATA_DIR;
+use crate::parser::cell_value::*;
+use crate::parser::TableModel;
+use super::Emitter;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, Write, Result};
+
+pub const MAGIC: &[u8; 4] = b"CFGB";
+pub const FORMAT_VERSION: u8 = 2;
+
+// layout of one value, parsed from the type row of the Template sheet
+pub enum Shape {
+    Bool,
+    SByte,
+    Byte,
+    Short,
+    UShort,
+    Int,    // LStrings too, as ids
+    UInt,
+    Float,
+    Double,
+    String,
+    Enum,   // value of the member
+    Custom, // raw text of the cell in the string pool
+    ShortList,
+    Array(Box<Shape>),
+    List(Box<Shape>),
+    Tuple(Vec<Shape>),
+    ValueTuple(Vec<Shape>),
+}
+
+// {Name}.bytes into the data dir:
+//   "CFGB", version(u8), schema hash(u32 le), rows(varint),
+//   string pool: count(varint), (len(varint), utf8)*,
+//   rows: every column but the first, which is the row index
+// short/int/enums zigzag varints, ushort/uint varints, float/double le,
+// strings/customs pool index + 1 (0 is null),
+// arrays/lists/ShortLists count + 1 (0 is null), tuples a presence byte
+pub struct BinaryEmitter;
+
+impl Emitter for BinaryEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_DATA_DIR })?;
+        let output_path = format!("{}/{}.bytes", unsafe { OUTPUT_DATA_DIR }, model.name);
+        let buf = encode(model)?;
+
+        let mut file = File::create(output_path)?;
+        file.write_all(&buf)?;
+        file.flush()
+    }
+}
+
+// the whole {Name}.bytes
+pub fn encode(model: &TableModel) -> Result<Vec<u8>> {
+    let shapes = model.columns.iter().skip(1).map(|v| shape_of(&v.ty)).collect::<Vec<Shape>>();
+    let mut pool = StringPool::default();
+    let mut rows = Vec::<u8>::default();
+    for row in 0..model.lines {
+        for (column, shape) in model.columns.iter().skip(1).zip(shapes.iter()) {
+            write_value(model, shape, column.value(row), &mut pool, &mut rows).map_err(|_| {
+                Error::new(ErrorKind::InvalidData, format!("{}: row {} of {} doesn't match its type {}", model.name, row, column.ident, column.ty))
+            })?;
+        }
+    }
+
+    let mut buf = Vec::<u8>::with_capacity(rows.len() + 64);
+    buf.extend_from_slice(MAGIC);
+    buf.push(FORMAT_VERSION);
+    buf.extend_from_slice(&schema_hash(model).to_le_bytes());
+    write_varint(model.lines as u32, &mut buf);
+    write_varint(pool.values.len() as u32, &mut buf);
+    for v in pool.values.iter() {
+        write_varint(v.len() as u32, &mut buf);
+        buf.extend_from_slice(v.as_bytes());
+    }
+    buf.extend_from_slice(&rows);
+    Ok(buf)
+}
+
+// FNV-1a of the identifiers and types of the written columns, readers refuse stale data
+pub fn schema_hash(model: &TableModel) -> u32 {
+    let mut hash = 0x811c9dc5_u32;
+    for column in model.columns.iter().skip(1) {
+        for b in column.ident.bytes().chain(std::iter::once(b':')).chain(column.ty.bytes()).chain(std::iter::once(b';')) {
+            hash ^= b as u32;
+            hash = hash.wrapping_mul(0x01000193);
+        }
+    }
+    hash
+}
+
+// int, List<int>, int[], enum:Base.Name[], ValueTuple<int,List<int>>, ...
+pub fn shape_of(ty: &str) -> Shape {
+    let mut chars = ty.chars().filter(|c| !c.is_whitespace()).peekable();
+    parse_shape(&mut chars)
+}
+
+fn parse_shape<I: Iterator<Item = char>>(chars: &mut std::iter::Peekable<I>) -> Shape {
+    let mut name = String::default();
+    while let Some(&c) = chars.peek() {
+        if c == '<' || c == '>' || c == ',' || c == '[' {
+            break;
+        }
+        name.push(c);
+        chars.next();
+    }
+
+    let mut args = Vec::default();
+    if chars.peek() == Some(&'<') {
+        chars.next();
+        loop {
+            args.push(parse_shape(chars));
+            match chars.next() {
+                Some(',') => continue,
+                _ => break,
+            }
+        }
+    }
+
+    let mut ret = match name.as_str() {
+        "List" if args.len() == 1 => Shape::List(Box::new(args.pop().unwrap())),
+        "Tuple" => Shape::Tuple(args),
+        "ValueTuple" => Shape::ValueTuple(args),
+        "bool" => Shape::Bool,
+        "sbyte" => Shape::SByte,
+        "byte" => Shape::Byte,
+        "short" => Shape::Short,
+        "ushort" => Shape::UShort,
+        "int" | "LString" | "Lstring" => Shape::Int,
+        "uint" => Shape::UInt,
+        "float" => Shape::Float,
+        "double" => Shape::Double,
+        "string" => Shape::String,
+        "ShortList" => Shape::ShortList,
+        s if s == "enum" || s.starts_with("enum:") => Shape::Enum,
+        _ => Shape::Custom,
+    };
+
+    // fixed lengths are dropped, int[3] -> int[]
+    while chars.peek() == Some(&'[') {
+        while let Some(c) = chars.next() {
+            if c == ']' {
+                break;
+            }
+        }
+        ret = Shape::Array(Box::new(ret));
+    }
+    ret
+}
+
+#[derive(Default)]
+struct StringPool {
+    values: Vec<String>,
+    index: HashMap<String, u32>,
+}
+
+impl StringPool {
+    // 0 is kept for null
+    fn add(&mut self, v: &str) -> u32 {
+        if let Some(idx) = self.index.get(v) {
+            return *idx;
+        }
+        self.values.push(String::from(v));
+        let idx = self.values.len() as u32;
+        self.index.insert(String::from(v), idx);
+        idx
+    }
+}
+
+fn write_value(model: &TableModel, shape: &Shape, v: &CellValue, pool: &mut StringPool, buf: &mut Vec<u8>) -> std::result::Result<(), ()> {
+    match (shape, v) {
+        (Shape::Bool, CellValue::DBool(BoolValue(b))) => buf.push(*b as u8),
+        (Shape::SByte, CellValue::DSByte(SByteValue(n))) => buf.push(*n as u8),
+        (Shape::Byte, CellValue::DByte(ByteValue(n))) => buf.push(*n),
+        (Shape::Short, CellValue::DShort(ShortValue(n))) => write_zigzag(*n as i32, buf),
+        (Shape::UShort, CellValue::DUShort(UShortValue(n))) => write_varint(*n as u32, buf),
+        (Shape::Int, CellValue::DInt(IntValue(n))) => write_zigzag(*n, buf),
+        (Shape::Int, CellValue::DLString(LStringValue(_, id))) => write_zigzag(*id, buf),
+        (Shape::UInt, CellValue::DUInt(UIntValue(n))) => write_varint(*n, buf),
+        (Shape::Float, CellValue::DFloat(FloatValue(n))) => buf.extend_from_slice(&n.to_le_bytes()),
+        (Shape::Double, CellValue::DDouble(DoubleValue(n))) => buf.extend_from_slice(&n.to_le_bytes()),
+        (Shape::String, CellValue::DString(StringValue(s))) => {
+            let s = s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s);
+            write_varint(pool.add(s), buf);
+        }
+        (Shape::Enum, CellValue::DEnum(EnumValue(name, ident, owner))) => {
+            // empty cells are the default member
+            let val = if ident.is_empty() { 0 } else { model.enum_value(owner, name, ident).ok_or(())? };
+            write_zigzag(val, buf);
+        }
+        (Shape::Custom, CellValue::DCustom(CustomValue(_, params))) => write_varint(pool.add(params), buf),
+        (Shape::ShortList, CellValue::DShortList(ShortListValue(ArrayValue(arr)))) => {
+            write_varint(arr.len() as u32, buf);
+            for v in arr.iter().skip(1) {
+                write_value(model, &Shape::Short, v, pool, buf)?;
+            }
+        }
+        (Shape::Array(inner), CellValue::DArray(ArrayValue(arr))) | (Shape::List(inner), CellValue::DList(ListValue(arr))) => {
+            // the dummy prototype is not written, len is count + 1
+            write_varint(arr.len() as u32, buf);
+            for v in arr.iter().skip(1) {
+                write_value(model, inner, v, pool, buf)?;
+            }
+        }
+        (Shape::Tuple(shapes), CellValue::DTuple(TupleValue(arr))) | (Shape::ValueTuple(shapes), CellValue::DValueTuple(ValueTupleValue(arr))) => {
+            if shapes.len() != arr.len() {
+                return Err(());
+            }
+            buf.push(1);
+            for (shape, v) in shapes.iter().zip(arr.iter()) {
+                write_value(model, shape, v, pool, buf)?;
+            }
+        }
+        // empty cells
+        (shape, CellValue::DNone(_)) => write_none(shape, buf),
+        _ => return Err(()),
+    }
+    Ok(())
+}
+
+fn write_none(shape: &Shape, buf: &mut Vec<u8>) {
+    match shape {
+        Shape::Bool | Shape::Byte | Shape::Tuple(_) | Shape::ValueTuple(_) => buf.push(0),
+        Shape::SByte => buf.push(-1_i8 as u8),
+        Shape::Short | Shape::Int => write_zigzag(-1, buf),
+        Shape::Float => buf.extend_from_slice(&0_f32.to_le_bytes()),
+        Shape::Double => buf.extend_from_slice(&0_f64.to_le_bytes()),
+        _ => write_varint(0, buf),
+    }
+}
+
+fn write_varint(mut v: u32, buf: &mut Vec<u8>) {
+    while v >= 0x80 {
+        buf.push((v as u8 & 0x7f) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+fn write_zigzag(v: i32, buf: &mut Vec<u8>) {
+    write_varint(((v << 1) ^ (v >> 31)) as u32, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{Column, EnumDef};
+    use std::collections::BTreeSet;
+    use std::rc::Rc;
+
+    struct Reader<'a>(&'a [u8]);
+
+    impl<'a> Reader<'a> {
+        fn byte(&mut self) -> u8 {
+            let b = self.0[0];
+            self.0 = &self.0[1..];
+            b
+        }
+
+        fn uint(&mut self) -> u32 {
+            let (mut v, mut shift) = (0_u32, 0);
+            loop {
+                let b = self.byte();
+                v |= ((b & 0x7f) as u32) << shift;
+                if b < 0x80 {
+                    return v;
+                }
+                shift += 7;
+            }
+        }
+
+        fn int(&mut self) -> i32 {
+            let v = self.uint();
+            ((v >> 1) as i32) ^ -((v & 1) as i32)
+        }
+    }
+
+    fn rc(v: &str) -> Rc<String> {
+        Rc::new(String::from(v))
+    }
+
+    fn enum_cell(ident: &str) -> CellValue {
+        CellValue::DEnum(EnumValue(rc("Kind"), rc(ident), rc("Item")))
+    }
+
+    fn model(kinds: Vec<CellValue>) -> TableModel {
+        let ints = |v: &[i32]| CellValue::DList(ListValue(std::iter::once(CellValue::DInt(IntValue(0))).chain(v.iter().map(|n| CellValue::DInt(IntValue(*n)))).collect()));
+        TableModel {
+            name: String::from("Item"),
+            columns: vec![
+                Column::new("#RefName", "string", vec![CellValue::DNone(NoneValue(rc("string"))), CellValue::DNone(NoneValue(rc("string")))]),
+                Column::new("Id", "int", vec![CellValue::DInt(IntValue(-3)), CellValue::DInt(IntValue(300))]),
+                Column::new("Name", "string", vec![CellValue::DString(StringValue(rc("\"sword\""))), CellValue::DString(StringValue(rc("sword")))]),
+                Column::new("Kind", "enum", kinds),
+                Column::new("Costs", "List<int>", vec![ints(&[1, -2]), ints(&[])]),
+            ],
+            lines: 2,
+            enums: vec![EnumDef {
+                owner: String::from("Item"),
+                name: String::from("Kind"),
+                members: vec![(rc("None"), 0, rc("")), (rc("Weapon"), 200, rc(""))],
+                reserved: BTreeSet::default(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rows_read_back() {
+        let model = model(vec![enum_cell("Weapon"), enum_cell("")]);
+        let buf = encode(&model).unwrap();
+        let mut r = Reader(&buf);
+
+        assert_eq!(&[r.byte(), r.byte(), r.byte(), r.byte()], MAGIC);
+        assert_eq!(r.byte(), FORMAT_VERSION);
+        let hash = [r.byte(), r.byte(), r.byte(), r.byte()];
+        assert_eq!(u32::from_le_bytes(hash), schema_hash(&model));
+        assert_eq!(r.uint(), 2);
+        assert_eq!(r.uint(), 1);
+        let len = r.uint() as usize;
+        assert_eq!(&r.0[..len], b"sword");
+        r.0 = &r.0[len..];
+
+        // Id, Name, Kind, Costs
+        assert_eq!((r.int(), r.uint(), r.int()), (-3, 1, 200));
+        assert_eq!((r.uint(), r.int(), r.int()), (3, 1, -2));
+        assert_eq!((r.int(), r.uint(), r.int()), (300, 1, 0));
+        assert_eq!(r.uint(), 1);
+        assert!(r.0.is_empty());
+    }
+
+    #[test]
+    fn mismatched_cells_are_errors() {
+        assert!(encode(&model(vec![enum_cell("Weapon"), enum_cell("Shield")])).is_err());
+        assert!(encode(&model(vec![enum_cell("Weapon"), CellValue::DInt(IntValue(1))])).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use crate::defs::{OUTPUT_CPP_DIR, LINE_END_FLAG};
+use crate::naming;
+use crate::parser::cell_value::*;
+use crate::parser::{TableModel, EnumDef};
+use super::Emitter;
+use super::binary::{Shape, shape_of};
+use super::csharp::{param_type, generic_args};
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{Write, Result};
+
+// {Name}.h into the cpp dir: struct {Name}Item by the naming of the workbook, the DefKeys as constexpr ids and the rows
+// baked into {Name}Rows(). E{Base}{Name}.h are enum classes, included by the tables which use them
+pub struct CppEmitter;
+
+impl Emitter for CppEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_CPP_DIR })?;
+        for def in model.enums.iter() {
+            self.emit_enum(def)?;
+        }
+
+        let name = &model.name;
+        let item_name = naming::of(name).item_class(name);
+        let end = LINE_END_FLAG;
+        let mut includes = BTreeSet::<String>::default();
+
+        let mut fields = Vec::<u8>::default();
+        let mut types = Vec::<String>::default();
+        for column in model.columns.iter() {
+            if let Some(comment) = column.comment.as_ref() {
+                fields.write_fmt(format_args!("\t// {}{}", comment.replace(['\r', '\n'], " "), end))?;
+            }
+            let ty = cpp_type(&shape_of(&column.ty), &param_type(column, name), &mut includes);
+            fields.write_fmt(format_args!("\t{} {};{}", ty, column.ident, end))?;
+            types.push(ty);
+        }
+
+        let mut rows = Vec::<u8>::default();
+        for row in 0..model.lines {
+            rows.write("\t\t{ ".as_bytes())?;
+            for (i, column) in model.columns.iter().enumerate() {
+                if i > 0 {
+                    rows.write(", ".as_bytes())?;
+                }
+                write_value(column.value(row), &types[i], &mut rows)?;
+            }
+            rows.write_fmt(format_args!(" }},{}", end))?;
+        }
+
+        let output_path = format!("{}/{}.h", unsafe { OUTPUT_CPP_DIR }, name);
+        let mut file = File::create(output_path)?;
+        file.write_fmt(format_args!("// This File is generated by the program, DO NOT EDIT MANUALLY!{}{}", end, end))?;
+        file.write_fmt(format_args!("#pragma once{}{}", end, end))?;
+        for v in ["cstdint", "limits", "string", "tuple", "vector"] {
+            file.write_fmt(format_args!("#include <{}>{}", v, end))?;
+        }
+        for v in includes.iter() {
+            file.write_fmt(format_args!("#include \"{}.h\"{}", v, end))?;
+        }
+        file.write_fmt(format_args!("{}namespace Config{}{{{}", end, end, end))?;
+
+        file.write_fmt(format_args!("struct {}{}{{{}", item_name, end, end))?;
+        file.write(&fields)?;
+        file.write_fmt(format_args!("}};{}", end))?;
+
+        let def_keys = model.def_keys();
+        if !def_keys.is_empty() {
+            file.write_fmt(format_args!("{}namespace {}DefKey{}{{{}", end, name, end, end))?;
+            for (key, id) in def_keys.iter() {
+                file.write_fmt(format_args!("\tconstexpr {} {} = {};{}", types[0], key, id, end))?;
+            }
+            file.write_fmt(format_args!("}}{}", end))?;
+        }
+
+        // built on first use, the order of static initialization across headers does not matter
+        file.write_fmt(format_args!("{}inline const std::vector<{}>& {}Rows(){}{{{}", end, item_name, name, end, end))?;
+        file.write_fmt(format_args!("\tstatic const std::vector<{}> rows = {{{}", item_name, end))?;
+        file.write(&rows)?;
+        file.write_fmt(format_args!("\t}};{}", end))?;
+        file.write_fmt(format_args!("\treturn rows;{}", end))?;
+        file.write_fmt(format_args!("}}{}", end))?;
+        file.write_fmt(format_args!("}}{}", end))?;
+        file.flush()
+    }
+
+    fn emit_enum(&self, def: &EnumDef) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_CPP_DIR })?;
+        let type_name = naming::enum_type(&def.owner, &def.name);
+        let output_path = format!("{}/{}.h", unsafe { OUTPUT_CPP_DIR }, type_name);
+        let mut file = File::create(output_path)?;
+        let end = LINE_END_FLAG;
+
+        file.write_fmt(format_args!("// This File is generated by the program, DO NOT EDIT MANUALLY!{}{}", end, end))?;
+        file.write_fmt(format_args!("#pragma once{}{}", end, end))?;
+        file.write_fmt(format_args!("#include <cstdint>{}{}", end, end))?;
+        file.write_fmt(format_args!("namespace Config{}{{{}", end, end))?;
+        file.write_fmt(format_args!("enum class {} : int32_t{}{{{}", type_name, end, end))?;
+        for (ident, val, desc) in def.members.iter() {
+            file.write_fmt(format_args!("\t{} = {}, // {}{}", ident, val, desc.replace(['\r', '\n'], " "), end))?;
+        }
+        file.write_fmt(format_args!("}};{}", end))?;
+        file.write_fmt(format_args!("}}{}", end))?;
+        file.flush()
+    }
+}
+
+// the headers of the enums are collected into includes
+fn cpp_type(shape: &Shape, ty: &str, includes: &mut BTreeSet<String>) -> String {
+    match shape {
+        Shape::Bool => String::from("bool"),
+        Shape::SByte => String::from("int8_t"),
+        Shape::Byte => String::from("uint8_t"),
+        Shape::Short => String::from("int16_t"),
+        Shape::UShort => String::from("uint16_t"),
+        Shape::Int => String::from("int32_t"),
+        Shape::UInt => String::from("uint32_t"),
+        Shape::Float => String::from("float"),
+        Shape::Double => String::from("double"),
+        Shape::String | Shape::Custom => String::from("std::string"),
+        Shape::ShortList => String::from("std::vector<int16_t>"),
+        Shape::Enum => {
+            includes.insert(String::from(ty));
+            String::from(ty)
+        }
+        Shape::Array(inner) | Shape::List(inner) => {
+            let inner_ty = match shape {
+                Shape::Array(_) => String::from(ty.strip_suffix("[]").unwrap_or(ty)),
+                _ => generic_args(ty).pop().unwrap_or_default(),
+            };
+            format!("std::vector<{}>", cpp_type(inner, &inner_ty, includes))
+        }
+        Shape::Tuple(shapes) | Shape::ValueTuple(shapes) => {
+            let items = shapes.iter().zip(generic_args(ty).iter()).map(|(s, t)| cpp_type(s, t, includes)).collect::<Vec<String>>();
+            format!("std::tuple<{}>", items.join(", "))
+        }
+    }
+}
+
+// ty is the C++ type of the value; empty strings, lists, tuples and enums are value-initialized
+fn write_value<W: Write + ?Sized>(v: &CellValue, ty: &str, stream: &mut W) -> Result<()> {
+    match v {
+        CellValue::DEnum(EnumValue(name, val, owner)) => {
+            if val.is_empty() {
+                stream.write_fmt(format_args!("{}{{}}", ty))?;
+            } else {
+                stream.write_fmt(format_args!("{}::{}", naming::enum_type(owner, name), val))?;
+            }
+        }
+        CellValue::DBool(BoolValue(b)) => { stream.write(b.to_string().as_bytes())?; }
+        CellValue::DLString(LStringValue(_, id)) => { stream.write(int_str(*id).as_bytes())?; }
+        CellValue::DString(StringValue(s)) => {
+            let s = s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s);
+            stream.write_fmt(format_args!("\"{}\"", escape_str(s)))?;
+        }
+        CellValue::DShort(ShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DUShort(UShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DSByte(SByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DByte(ByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DInt(IntValue(n)) => { stream.write(int_str(*n).as_bytes())?; }
+        CellValue::DUInt(UIntValue(n)) => { stream.write_fmt(format_args!("{}u", n))?; }
+        CellValue::DFloat(FloatValue(n)) => { stream.write(float_str(*n as f64, n.to_string(), "float").as_bytes())?; }
+        CellValue::DDouble(DoubleValue(n)) => { stream.write(float_str(*n, n.to_string(), "double").as_bytes())?; }
+        CellValue::DCustom(CustomValue(_, params)) => { stream.write_fmt(format_args!("\"{}\"", escape_str(params)))?; }
+        CellValue::DShortList(ShortListValue(ArrayValue(arr))) => { write_array(arr.iter().skip(1), ty, stream)?; }
+        CellValue::DTuple(TupleValue(arr)) => { write_array(arr.iter(), ty, stream)?; }
+        CellValue::DValueTuple(ValueTupleValue(arr)) => { write_array(arr.iter(), ty, stream)?; }
+        CellValue::DArray(ArrayValue(arr)) => { write_array(arr.iter().skip(1), ty, stream)?; }
+        CellValue::DList(ListValue(arr)) => { write_array(arr.iter().skip(1), ty, stream)?; }
+        CellValue::DNone(NoneValue(t)) => {
+            match super::empty_literal(t) {
+                Some(v) if ty == "float" => { stream.write_fmt(format_args!("{}f", v))?; }
+                Some(v) => { stream.write(v.as_bytes())?; }
+                None => { stream.write_fmt(format_args!("{}{{}}", ty))?; }
+            }
+        }
+        CellValue::DError(_) => { stream.write_fmt(format_args!("{}{{}}", ty))?; }
+    }
+    Ok(())
+}
+
+// the elements of vectors and tuples are initialized by their own types
+fn write_array<'a, W: Write + ?Sized>(arr: impl Iterator<Item = &'a CellValue>, ty: &str, stream: &mut W) -> Result<()> {
+    let args = generic_args(ty);
+    stream.write("{".as_bytes())?;
+    for (i, v) in arr.enumerate() {
+        if i > 0 {
+            stream.write(", ".as_bytes())?;
+        }
+        let elem = if ty.starts_with("std::tuple<") { args.get(i) } else { args.first() };
+        write_value(v, elem.map_or("", |v| v.as_str()), stream)?;
+    }
+    stream.write("}".as_bytes())?;
+    Ok(())
+}
+
+// -2147483648 is the negation of a literal which does not fit into int
+fn int_str(v: i32) -> String {
+    if v == i32::MIN {
+        String::from("(-2147483647 - 1)")
+    } else {
+        v.to_string()
+    }
+}
+
+// s is the shortest text of the value
+fn float_str(v: f64, s: String, ty: &str) -> String {
+    if v.is_nan() {
+        format!("std::numeric_limits<{}>::quiet_NaN()", ty)
+    } else if v.is_infinite() {
+        format!("{}std::numeric_limits<{}>::infinity()", if v > 0.0 { "" } else { "-" }, ty)
+    } else {
+        let s = if s.contains(|c| c == '.' || c == 'e') { s } else { format!("{}.0", s) };
+        if ty == "float" { format!("{}f", s) } else { s }
+    }
+}
+
+// other control characters as octal escapes, hex escapes would take the following digits too
+fn escape_str(v: &str) -> String {
+    let mut ret = String::with_capacity(v.len());
+    for c in v.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 || c as u32 == 0x7f => ret.push_str(&format!("\\{:03o}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret
+}
@@ -0,0 +1,512 @@
+use crate::defs::*;
+use crate::naming;
+use crate::parser::{TableModel, EnumDef, EnumMember};
+use super::Emitter;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{Write, Result};
+
+use item_class::ItemClass;
+pub(crate) use item_class::param_type;
+mod item_class;
+
+use base_class::BaseClass;
+mod base_class;
+
+pub mod value;
+
+pub mod template;
+
+pub mod collections;
+
+mod pool;
+
+trait CodeGenerator {
+    fn gen_code<W: Write + ?Sized>(&self, end: &'static str, tab_nums: i32, stream: &mut W) -> Result<()>;
+}
+
+// where the rows of the generated classes come from
+#[derive(Clone, Copy, PartialEq)]
+pub enum DataSource {
+    Code,   // baked into CreateItemsN
+    Json,   // read by ConfigJson from the exported {Name}.json
+    Binary, // read by ConfigBinary from the exported {Name}.bytes
+}
+
+// {Name}Item and {Name} into the script dir, E{Base}{Name} into the enum dir
+pub struct CSharpEmitter {
+    pub data: DataSource,
+}
+
+impl Emitter for CSharpEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        for def in model.enums.iter() {
+            self.emit_enum(def)?;
+        }
+
+        let output_path = format!("{}/{}.{}", unsafe { OUTPUT_SCRIPT_CODE_DIR }, model.name, DEFAULT_DEST_SUFFIX);
+        let mut file = File::create(output_path)?;
+        gen_table(model, self.data, LINE_END_FLAG, &mut file)
+    }
+
+    fn emit_enum(&self, def: &EnumDef) -> Result<()> {
+        let type_name = naming::enum_type(&def.owner, &def.name);
+        let dest = format!("{}/{}.cs", unsafe { OUTPUT_ENUM_CODE_DIR }, type_name);
+        let summary = if def.owner.is_empty() { def.name.clone() } else { format!("{} -> {}", def.owner, def.name) };
+        let namespace = naming::of(&def.owner).enum_namespace();
+        write_enum(&dest, namespace, &type_name, &summary, &def.members, &def.reserved)?;
+
+        if unsafe { GEN_ENUM_HELPERS } {
+            gen_enum_helper(namespace, &type_name, &def.members)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<()> {
+        if pool::saved() > 0 {
+            println!("literal pools: {} bytes saved", pool::saved());
+        }
+        match self.data {
+            DataSource::Json => gen_json_loader(),
+            DataSource::Binary => gen_binary_loader(),
+            DataSource::Code => Ok(()),
+        }
+    }
+}
+
+fn gen_table<W: Write + ?Sized>(model: &TableModel, data: DataSource, end: &'static str, stream: &mut W) -> Result<()> {
+    let mut item_class = Vec::<u8>::default();
+    ItemClass { model }.gen_code(end, 1, &mut item_class)?;
+    let mut base_class = Vec::<u8>::default();
+    BaseClass { model, data }.gen_code(end, 1, &mut base_class)?;
+
+    // banner, usings and namespace around the classes
+    let naming = naming::of(&model.name);
+    let mut usings = naming.usings.iter().map(|v| format!("using {};", v)).collect::<Vec<String>>();
+    if naming.namespace != naming::project().namespace {
+        usings.push(format!("using {};", naming::project().namespace));
+    }
+    for using in naming::enum_usings(model) {
+        if using != naming::project().namespace && !naming.usings.contains(&using) {
+            usings.push(format!("using {};", using));
+        }
+    }
+    for using in collections::usings() {
+        if !naming.usings.iter().any(|v| v == using) {
+            usings.push(format!("using {};", using));
+        }
+    }
+    let text = template::render("file.tpl", &[
+        ("Name", &model.name),
+        ("Namespace", &naming.namespace),
+        ("Usings", &usings.join(end)),
+        ("ItemClass", &String::from_utf8_lossy(&item_class)),
+        ("BaseClass", &String::from_utf8_lossy(&base_class)),
+    ])?;
+    stream.write(text.as_bytes())?;
+    Ok(())
+}
+
+fn write_enum(dest: &str, namespace: Option<&str>, type_name: &str, summary: &str, members: &Vec<EnumMember>, reserved: &BTreeSet<i32>) -> Result<()> {
+    let mut file = Vec::<u8>::default();
+    file.write("/// <summary>".as_bytes())?;
+    file.write(LINE_END_FLAG.as_bytes())?;
+    file.write_fmt(format_args!("/// {}{}", summary, LINE_END_FLAG))?;
+    file.write("/// </summary>".as_bytes())?;
+    file.write(LINE_END_FLAG.as_bytes())?;
+    file.write_fmt(format_args!("public enum {}{}", type_name, LINE_END_FLAG))?;
+    file.write("{".as_bytes())?;
+    file.write(LINE_END_FLAG.as_bytes())?;
+
+    for (ident, val, desc) in members.iter() {
+        file.write_fmt(format_args!("{}/// <summary>{}", '\t', LINE_END_FLAG))?;
+        for line in desc.lines() {
+            file.write_fmt(format_args!("{}/// {}{}", '\t', line, LINE_END_FLAG))?;
+        }
+        file.write_fmt(format_args!("{}/// </summary>{}", '\t', LINE_END_FLAG))?;
+        file.write_fmt(format_args!("{}{} = {},{}", '\t', ident, val, LINE_END_FLAG))?;
+    }
+
+    if !reserved.is_empty() {
+        let vals = reserved.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+        file.write_fmt(format_args!("{}{}{}{}", '\t', ENUM_RESERVED_FLAG, vals.join(", "), LINE_END_FLAG))?;
+    }
+    let count = members.iter().map(|v| v.1 + 1).max().unwrap_or(0);
+    file.write_fmt(format_args!("{}Count = {}{}", '\t', count, LINE_END_FLAG))?;
+    file.write("}".as_bytes())?;
+    write_in_namespace(dest, &["#pragma warning disable 1591"], namespace, &file)
+}
+
+// header lines, a blank line and the body, indented in the namespace if there is one
+fn write_in_namespace(dest: &str, header: &[&str], namespace: Option<&str>, body: &[u8]) -> Result<()> {
+    let end = LINE_END_FLAG;
+    let mut file = File::create(dest)?;
+    for line in header.iter() {
+        file.write_fmt(format_args!("{}{}", line, end))?;
+    }
+    file.write(end.as_bytes())?;
+    match namespace {
+        Some(namespace) => {
+            file.write_fmt(format_args!("namespace {}{}{{{}", namespace, end, end))?;
+            for line in String::from_utf8_lossy(body).split(end) {
+                if !line.is_empty() {
+                    file.write_fmt(format_args!("\t{}", line))?;
+                }
+                file.write(end.as_bytes())?;
+            }
+            file.write("}".as_bytes())?;
+        }
+        None => { file.write(body)?; }
+    }
+    file.flush()
+}
+
+// E{Name}Helper: descriptions, parsing by identifier or description, defined values
+fn gen_enum_helper(namespace: Option<&str>, type_name: &str, members: &Vec<EnumMember>) -> Result<()> {
+    let dest = format!("{}/{}Helper.cs", unsafe { OUTPUT_ENUM_CODE_DIR }, type_name);
+    let mut file = Vec::<u8>::default();
+    let end = LINE_END_FLAG;
+
+    file.write_fmt(format_args!("/// <summary>{}/// helpers of {}{}/// </summary>{}", end, type_name, end, end))?;
+    file.write_fmt(format_args!("public static class {}Helper{}{{{}", type_name, end, end))?;
+
+    let values = members.iter().map(|v| format!("{}.{}", type_name, v.0)).collect::<Vec<String>>();
+    file.write_fmt(format_args!("\tpublic static readonly {}[] Values = {{ {} }};{}{}", type_name, values.join(", "), end, end))?;
+
+    file.write_fmt(format_args!("\tprivate static readonly Dictionary<{}, string> _descriptions = new Dictionary<{}, string>(){}\t{{{}", type_name, type_name, end, end))?;
+    for (ident, _, desc) in members.iter() {
+        file.write_fmt(format_args!("\t\t{{ {}.{}, \"{}\" }},{}", type_name, ident, escape_str(desc), end))?;
+    }
+    file.write_fmt(format_args!("\t}};{}{}", end, end))?;
+
+    file.write_fmt(format_args!("\tprivate static readonly Dictionary<string, {}> _values = new Dictionary<string, {}>(){}\t{{{}", type_name, type_name, end, end))?;
+    for (ident, _, _) in members.iter() {
+        file.write_fmt(format_args!("\t\t{{ \"{}\", {}.{} }},{}", ident, type_name, ident, end))?;
+    }
+    // identifiers win when a description equals one of them
+    for (ident, _, desc) in members.iter().filter(|v| !members.iter().any(|m| m.0 == v.2)) {
+        file.write_fmt(format_args!("\t\t{{ \"{}\", {}.{} }},{}", escape_str(desc), type_name, ident, end))?;
+    }
+    file.write_fmt(format_args!("\t}};{}{}", end, end))?;
+
+    file.write_fmt(format_args!("\tpublic static string GetDescription(this {} value) => _descriptions.TryGetValue(value, out var desc) ? desc : null;{}{}", type_name, end, end))?;
+    file.write_fmt(format_args!("\tpublic static bool TryParse(string text, out {} value) => _values.TryGetValue(text, out value);{}{}", type_name, end, end))?;
+    file.write_fmt(format_args!("\tpublic static bool IsDefined({} value) => _descriptions.ContainsKey(value);{}", type_name, end))?;
+    file.write("}".as_bytes())?;
+    write_in_namespace(&dest, &["#pragma warning disable 1591", "", "using System.Collections.Generic;"], namespace, &file)
+}
+
+// ConfigJson: reads the rows exported by the json format, uses Newtonsoft.Json
+fn gen_json_loader() -> Result<()> {
+    let dest = format!("{}/ConfigJson.{}", unsafe { OUTPUT_SCRIPT_CODE_DIR }, DEFAULT_DEST_SUFFIX);
+    let mut file = File::create(dest)?;
+    let end = LINE_END_FLAG;
+    let lines = [
+        "using System;",
+        "using System.IO;",
+        "using Newtonsoft.Json;",
+        "using Newtonsoft.Json.Linq;",
+    ];
+    for line in lines.iter() {
+        file.write_fmt(format_args!("{}{}", line, end))?;
+    }
+    write_namespace(&mut file)?;
+    let lines = [
+        String::from("\tpublic static class ConfigJson"),
+        String::from("\t{"),
+        String::from("\t\t/// <summary>"),
+        String::from("\t\t/// dir of the exported {Name}.json, --output-data-dir of the build"),
+        String::from("\t\t/// </summary>"),
+        format!("\t\tpublic static string DataDir = \"{}\";", escape_str(unsafe { OUTPUT_DATA_DIR })),
+        String::default(),
+        String::from("\t\t/// <summary>"),
+        String::from("\t\t/// text of {Name}.json, replace it to load from somewhere else"),
+        String::from("\t\t/// </summary>"),
+        String::from("\t\tpublic static Func<string, string> LoadText = name => File.ReadAllText(Path.Combine(DataDir, name + \".json\"));"),
+    ];
+    for line in lines.iter() {
+        file.write_fmt(format_args!("{}{}", line, end))?;
+    }
+    let lines = [
+        "",
+        "\t\t/// <summary>",
+        "\t\t/// add converters of the custom types here",
+        "\t\t/// </summary>",
+        "\t\tpublic static readonly JsonSerializer Serializer = JsonSerializer.Create(new JsonSerializerSettings",
+        "\t\t{",
+        "\t\t\tConverters = { new TupleConverter() }",
+        "\t\t});",
+        "",
+        "\t\tpublic static JArray LoadRows(string name) => JArray.Parse(LoadText(name));",
+        "",
+        "\t\tpublic static T Read<T>(JToken token)",
+        "\t\t{",
+        "\t\t\tif (token == null || token.Type == JTokenType.Null)",
+        "\t\t\t\treturn default;",
+        "\t\t\treturn token.ToObject<T>(Serializer);",
+        "\t\t}",
+        "",
+        "\t\t// tuples and ShortList are exported as arrays",
+        "\t\tprivate class TupleConverter : JsonConverter",
+        "\t\t{",
+        "\t\t\tpublic override bool CanWrite => false;",
+        "",
+        "\t\t\tpublic override bool CanConvert(Type objectType)",
+        "\t\t\t{",
+        "\t\t\t\tif (objectType == typeof(ShortList))",
+        "\t\t\t\t\treturn true;",
+        "\t\t\t\tif (!objectType.IsGenericType)",
+        "\t\t\t\t\treturn false;",
+        "\t\t\t\tvar name = objectType.GetGenericTypeDefinition().FullName;",
+        "\t\t\t\treturn name.StartsWith(\"System.Tuple`\") || name.StartsWith(\"System.ValueTuple`\");",
+        "\t\t\t}",
+        "",
+        "\t\t\tpublic override object ReadJson(JsonReader reader, Type objectType, object existingValue, JsonSerializer serializer)",
+        "\t\t\t{",
+        "\t\t\t\tvar array = JArray.Load(reader);",
+        "\t\t\t\tif (objectType == typeof(ShortList))",
+        "\t\t\t\t\treturn new ShortList(array.ToObject<short[]>());",
+        "\t\t\t\tvar types = objectType.GetGenericArguments();",
+        "\t\t\t\tvar args = new object[types.Length];",
+        "\t\t\t\tfor (int i = 0; i < types.Length; ++i)",
+        "\t\t\t\t\targs[i] = array[i].ToObject(types[i], serializer);",
+        "\t\t\t\treturn Activator.CreateInstance(objectType, args);",
+        "\t\t\t}",
+        "",
+        "\t\t\tpublic override void WriteJson(JsonWriter writer, object value, JsonSerializer serializer)",
+        "\t\t\t{",
+        "\t\t\t\tthrow new NotSupportedException();",
+        "\t\t\t}",
+        "\t\t}",
+        "\t}",
+        "}",
+    ];
+    for line in lines.iter() {
+        file.write_fmt(format_args!("{}{}", line, end))?;
+    }
+    file.flush()
+}
+
+// ConfigBinary: reads the rows exported by the bin format, see emitter::binary for the layout
+fn gen_binary_loader() -> Result<()> {
+    let dest = format!("{}/ConfigBinary.{}", unsafe { OUTPUT_SCRIPT_CODE_DIR }, DEFAULT_DEST_SUFFIX);
+    let mut file = File::create(dest)?;
+    let end = LINE_END_FLAG;
+    let magic = super::binary::MAGIC.iter().map(|v| format!("(byte)'{}'", *v as char)).collect::<Vec<String>>();
+    let lines = [
+        "using System;",
+        "using System.IO;",
+        "using System.Text;",
+        "using System.Collections.Generic;",
+    ];
+    for line in lines.iter() {
+        file.write_fmt(format_args!("{}{}", line, end))?;
+    }
+    write_namespace(&mut file)?;
+    let lines = [
+        String::from("\tpublic static class ConfigBinary"),
+        String::from("\t{"),
+        format!("\t\tpublic const byte Version = {};", super::binary::FORMAT_VERSION),
+        format!("\t\tprivate static readonly byte[] Magic = {{ {} }};", magic.join(", ")),
+        String::default(),
+        String::from("\t\t/// <summary>"),
+        String::from("\t\t/// dir of the exported {Name}.bytes, --output-data-dir of the build"),
+        String::from("\t\t/// </summary>"),
+        format!("\t\tpublic static string DataDir = \"{}\";", escape_str(unsafe { OUTPUT_DATA_DIR })),
+        String::default(),
+        String::from("\t\t/// <summary>"),
+        String::from("\t\t/// content of {Name}.bytes, replace it to load from a TextAsset or somewhere else"),
+        String::from("\t\t/// </summary>"),
+        String::from("\t\tpublic static Func<string, byte[]> LoadBytes = name => File.ReadAllBytes(Path.Combine(DataDir, name + \".bytes\"));"),
+        String::default(),
+        String::from("\t\t/// <summary>"),
+        String::from("\t\t/// custom types are exported as the raw text of the cell, register their parsers here"),
+        String::from("\t\t/// </summary>"),
+        String::from("\t\tpublic static readonly Dictionary<Type, Func<string, object>> CustomParsers = new Dictionary<Type, Func<string, object>>();"),
+        String::default(),
+        String::from("\t\tpublic static Reader Open(string name, uint schemaHash) => new Reader(name, LoadBytes(name), schemaHash);"),
+        String::default(),
+        String::from("\t\tpublic sealed class Reader"),
+        String::from("\t\t{"),
+        String::from("\t\t\tpublic readonly int Count;"),
+        String::from("\t\t\tprivate readonly byte[] _data;"),
+        String::from("\t\t\tprivate readonly string[] _pool;"),
+        String::from("\t\t\tprivate int _pos;"),
+        String::default(),
+        String::from("\t\t\tpublic Reader(string name, byte[] data, uint schemaHash)"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\t_data = data;"),
+        String::from("\t\t\t\tfor (int i = 0; i < Magic.Length; ++i)"),
+        String::from("\t\t\t\t\tif (_data.Length <= Magic.Length || _data[i] != Magic[i])"),
+        String::from("\t\t\t\t\t\tthrow new Exception($\"{name}.bytes is not a config table.\");"),
+        String::from("\t\t\t\t_pos = Magic.Length;"),
+        String::from("\t\t\t\tif (_data[_pos++] != Version)"),
+        String::from("\t\t\t\t\tthrow new Exception($\"{name}.bytes has version {_data[_pos - 1]}, expected {Version}.\");"),
+        String::from("\t\t\t\tvar hash = (uint)(_data[_pos] | _data[_pos + 1] << 8 | _data[_pos + 2] << 16 | _data[_pos + 3] << 24);"),
+        String::from("\t\t\t\t_pos += 4;"),
+        String::from("\t\t\t\tif (hash != schemaHash)"),
+        String::from("\t\t\t\t\tthrow new Exception($\"{name}.bytes does not match the generated code, export them together.\");"),
+        String::from("\t\t\t\tCount = (int)ReadUInt();"),
+        String::from("\t\t\t\t_pool = new string[ReadUInt()];"),
+        String::from("\t\t\t\tfor (int i = 0; i < _pool.Length; ++i)"),
+        String::from("\t\t\t\t{"),
+        String::from("\t\t\t\t\tvar len = (int)ReadUInt();"),
+        String::from("\t\t\t\t\t_pool[i] = Encoding.UTF8.GetString(_data, _pos, len);"),
+        String::from("\t\t\t\t\t_pos += len;"),
+        String::from("\t\t\t\t}"),
+        String::from("\t\t\t}"),
+        String::default(),
+        String::from("\t\t\tpublic uint ReadUInt()"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\tuint ret = 0;"),
+        String::from("\t\t\t\tfor (int shift = 0; ; shift += 7)"),
+        String::from("\t\t\t\t{"),
+        String::from("\t\t\t\t\tvar b = _data[_pos++];"),
+        String::from("\t\t\t\t\tret |= (uint)(b & 0x7f) << shift;"),
+        String::from("\t\t\t\t\tif ((b & 0x80) == 0)"),
+        String::from("\t\t\t\t\t\treturn ret;"),
+        String::from("\t\t\t\t}"),
+        String::from("\t\t\t}"),
+        String::default(),
+        String::from("\t\t\tpublic int ReadInt()"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\tvar v = ReadUInt();"),
+        String::from("\t\t\t\treturn (int)(v >> 1) ^ -(int)(v & 1);"),
+        String::from("\t\t\t}"),
+        String::default(),
+        String::from("\t\t\tpublic bool ReadBool() => _data[_pos++] != 0;"),
+        String::from("\t\t\tpublic byte ReadByte() => _data[_pos++];"),
+        String::from("\t\t\tpublic sbyte ReadSByte() => (sbyte)_data[_pos++];"),
+        String::from("\t\t\tpublic short ReadShort() => (short)ReadInt();"),
+        String::from("\t\t\tpublic ushort ReadUShort() => (ushort)ReadUInt();"),
+        String::default(),
+        String::from("\t\t\t// little endian, as every platform Unity runs on"),
+        String::from("\t\t\tpublic float ReadFloat()"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\tvar ret = BitConverter.ToSingle(_data, _pos);"),
+        String::from("\t\t\t\t_pos += 4;"),
+        String::from("\t\t\t\treturn ret;"),
+        String::from("\t\t\t}"),
+        String::default(),
+        String::from("\t\t\tpublic double ReadDouble()"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\tvar ret = BitConverter.ToDouble(_data, _pos);"),
+        String::from("\t\t\t\t_pos += 8;"),
+        String::from("\t\t\t\treturn ret;"),
+        String::from("\t\t\t}"),
+        String::default(),
+        String::from("\t\t\tpublic string ReadString()"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\tvar idx = ReadUInt();"),
+        String::from("\t\t\t\treturn idx == 0 ? null : _pool[idx - 1];"),
+        String::from("\t\t\t}"),
+        String::default(),
+        String::from("\t\t\tpublic T ReadCustom<T>()"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\tvar text = ReadString();"),
+        String::from("\t\t\t\tif (text == null)"),
+        String::from("\t\t\t\t\treturn default;"),
+        String::from("\t\t\t\tif (!CustomParsers.TryGetValue(typeof(T), out var parser))"),
+        String::from("\t\t\t\t\tthrow new Exception($\"no parser of {typeof(T)} in ConfigBinary.CustomParsers.\");"),
+        String::from("\t\t\t\treturn (T)parser(text);"),
+        String::from("\t\t\t}"),
+        String::default(),
+        String::from("\t\t\tpublic ShortList ReadShortList()"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\tvar arr = ReadArray(ReadShort);"),
+        String::from("\t\t\t\treturn arr == null ? null : new ShortList(arr);"),
+        String::from("\t\t\t}"),
+        String::default(),
+        String::from("\t\t\tpublic T[] ReadArray<T>(Func<T> read)"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\tvar len = ReadUInt();"),
+        String::from("\t\t\t\tif (len == 0)"),
+        String::from("\t\t\t\t\treturn null;"),
+        String::from("\t\t\t\tvar ret = new T[len - 1];"),
+        String::from("\t\t\t\tfor (int i = 0; i < ret.Length; ++i)"),
+        String::from("\t\t\t\t\tret[i] = read();"),
+        String::from("\t\t\t\treturn ret;"),
+        String::from("\t\t\t}"),
+        String::default(),
+        String::from("\t\t\tpublic List<T> ReadList<T>(Func<T> read)"),
+        String::from("\t\t\t{"),
+        String::from("\t\t\t\tvar len = ReadUInt();"),
+        String::from("\t\t\t\tif (len == 0)"),
+        String::from("\t\t\t\t\treturn null;"),
+        String::from("\t\t\t\tvar ret = new List<T>((int)len - 1);"),
+        String::from("\t\t\t\tfor (int i = 0; i < len - 1; ++i)"),
+        String::from("\t\t\t\t\tret.Add(read());"),
+        String::from("\t\t\t\treturn ret;"),
+        String::from("\t\t\t}"),
+        String::from("\t\t}"),
+        String::from("\t}"),
+        String::from("}"),
+    ];
+    for line in lines.iter() {
+        file.write_fmt(format_args!("{}{}", line, end))?;
+    }
+    file.flush()
+}
+
+// the project usings and the opening of its namespace, after the usings of a loader
+fn write_namespace<W: Write + ?Sized>(stream: &mut W) -> Result<()> {
+    let end = LINE_END_FLAG;
+    let naming = naming::project();
+    for using in naming.project_usings() {
+        stream.write_fmt(format_args!("using {};{}", using, end))?;
+    }
+    stream.write_fmt(format_args!("{}#pragma warning disable 1591{}{}", end, end, end))?;
+    stream.write_fmt(format_args!("namespace {}{}{{{}", naming.namespace, end, end))
+}
+
+// Tuple<int,List<int>> -> [int, List<int>]
+pub(crate) fn generic_args(ty: &str) -> Vec<String> {
+    let inner = &ty[ty.find('<').map(|v| v + 1).unwrap_or(0)..ty.rfind('>').unwrap_or(ty.len())];
+    let mut ret = Vec::default();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                ret.push(String::from(inner[start..i].trim()));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    ret.push(String::from(inner[start..].trim()));
+    ret
+}
+
+fn escape_str(v: &str) -> String {
+    let mut ret = String::with_capacity(v.len());
+    for c in v.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            // the other line ends of C# end a string literal as well
+            c if (c as u32) < 0x20 || c as u32 == 0x7f || c == '\u{85}' || c == '\u{2028}' || c == '\u{2029}' => {
+                ret.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => ret.push(c),
+        }
+    }
+    ret
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::escape_str;
+
+    #[test]
+    fn escaped_descriptions_stay_on_one_line() {
+        assert_eq!(escape_str("a \"b\" \\c"), "a \\\"b\\\" \\\\c");
+        assert_eq!(escape_str("火\r\n\t水"), "火\\r\\n\\t水");
+        assert_eq!(escape_str("\0\u{7f}\u{2028}"), "\\u0000\\u007f\\u2028");
+    }
+}
@@ -0,0 +1,245 @@
+use crate::defs::{DEFAULT_LINES, POOL_LITERALS};
+use crate::naming;
+use crate::parser::{TableModel, KeyType};
+
+use crate::emitter::binary::{Shape, shape_of, schema_hash};
+use super::{CodeGenerator, DataSource, collections, generic_args, item_class::{param_type, lstring_column}, pool::LiteralPool, template};
+use std::io::{Write, Result};
+
+pub struct BaseClass<'a> {
+    pub model: &'a TableModel,
+    pub data: DataSource
+}
+
+impl<'a> CodeGenerator for BaseClass<'a> {
+    fn gen_code<W: Write + ?Sized>(&self, end: &'static str, tab_nums: i32, stream: &mut W) -> Result<()> {
+        let format = |n: i32, stream: &mut Vec<u8>| -> Result<()> {
+            for _ in 0..n {
+                stream.write("\t".as_bytes())?;
+            }
+            Ok(())
+        };
+
+        let name = &self.model.name;
+        let columns = &self.model.columns;
+        let lines = self.model.lines;
+        let id_type = &self.model.id_type;
+        let enumflags = &self.model.enumflags;
+        let naming = naming::of(name);
+        let item_name = naming.item_class(name);
+        // rows baked into CreateItemsN
+        let terms = if self.data == DataSource::Code { (lines / DEFAULT_LINES)+(if lines % DEFAULT_LINES == 0 {0} else {1}) } else { 0 };
+
+        // DefKey static class
+        let mut def_key = Vec::<u8>::default();
+        if let KeyType::DefKey(_) = self.model.key_type {
+            def_key.write(end.as_bytes())?;
+            format(tab_nums + 1, &mut def_key)?;
+            def_key.write("public static class DefKey".as_bytes())?;
+            def_key.write(end.as_bytes())?;
+            format(tab_nums + 1, &mut def_key)?;
+            def_key.write("{".as_bytes())?;
+            def_key.write(end.as_bytes())?;
+
+            for (key, id) in self.model.def_keys() {
+                format(tab_nums + 2, &mut def_key)?;
+                def_key.write_fmt(format_args!("public const {} {} = {};{}", id_type, key, id, end))?;
+            }
+
+            format(tab_nums + 1, &mut def_key)?;
+            def_key.write("}".as_bytes())?;
+        }
+
+        let mut create_items = Vec::<u8>::default();
+        let mut init_items = Vec::<String>::default();
+        // the literals shared by the rows go first
+        let pool = if terms > 0 && unsafe { POOL_LITERALS } { LiteralPool::new(self.model, tab_nums, end)? } else { LiteralPool::default() };
+        if !pool.is_empty() {
+            create_items.write(end.as_bytes())?;
+            create_items.write(pool.gen_fields(tab_nums, end).as_bytes())?;
+        }
+        let types = columns.iter().map(|v| collections::field_type(&param_type(v, name))).collect::<Vec<String>>();
+        for term in 0..terms {
+            create_items.write(end.as_bytes())?;
+            format(tab_nums + 1, &mut create_items)?;
+            create_items.write_fmt(format_args!("private void CreateItems{}(){}", term, end))?;
+            format(tab_nums + 1, &mut create_items)?;
+            create_items.write("{".as_bytes())?;
+            create_items.write(end.as_bytes())?;
+
+            let idx = term * DEFAULT_LINES;
+            let end_idx = if lines - idx < DEFAULT_LINES { lines } else { idx + DEFAULT_LINES };
+            for row in idx..end_idx {
+                format(tab_nums + 2, &mut create_items)?;
+                create_items.write_fmt(format_args!("_dataArray.Add(new {}({},", item_name, row))?;
+
+                for i in 1..columns.len() {
+                    create_items.write(pool.get(columns[i].value(row), &types[i])?.as_bytes())?;
+                    if i != columns.len()-1 {
+                        create_items.write(",".as_bytes())?;
+                    }
+                }
+
+                create_items.write("));".as_bytes())?;
+                create_items.write(end.as_bytes())?;
+                format(tab_nums + 2, &mut create_items)?;
+                create_items.write_fmt(format_args!("OnItemCreated(_dataArray[{}]);{}", row, end))?;
+            }
+
+            format(tab_nums + 1, &mut create_items)?;
+            create_items.write("}".as_bytes())?;
+            if term + 1 < terms {
+                create_items.write(end.as_bytes())?;
+            }
+            init_items.push(format!("{}CreateItems{}();", "\t".repeat(tab_nums as usize + 2), term));
+        }
+
+        let mut load_items = Vec::<u8>::default();
+        if self.data != DataSource::Code {
+            load_items.write(end.as_bytes())?;
+            format(tab_nums + 1, &mut load_items)?;
+            load_items.write_fmt(format_args!("private void LoadItems(){}", end))?;
+            format(tab_nums + 1, &mut load_items)?;
+            load_items.write_fmt(format_args!("{{{}", end))?;
+            format(tab_nums + 2, &mut load_items)?;
+            if self.data == DataSource::Json {
+                load_items.write_fmt(format_args!("var rows = ConfigJson.LoadRows(\"{}\");{}", name, end))?;
+                format(tab_nums + 2, &mut load_items)?;
+                load_items.write_fmt(format_args!("for (int i = 0; i < rows.Count; ++i){}", end))?;
+            } else {
+                load_items.write_fmt(format_args!("var reader = ConfigBinary.Open(\"{}\", 0x{:08X}u);{}", name, schema_hash(self.model), end))?;
+                format(tab_nums + 2, &mut load_items)?;
+                load_items.write_fmt(format_args!("for (int i = 0; i < reader.Count; ++i){}", end))?;
+            }
+            format(tab_nums + 2, &mut load_items)?;
+            load_items.write_fmt(format_args!("{{{}", end))?;
+            if self.data == DataSource::Json {
+                format(tab_nums + 3, &mut load_items)?;
+                load_items.write_fmt(format_args!("var row = rows[i];{}", end))?;
+            }
+            format(tab_nums + 3, &mut load_items)?;
+            load_items.write_fmt(format_args!("_dataArray.Add(new {}(", item_name))?;
+            for (i, column) in columns.iter().enumerate() {
+                let ty = param_type(column, name);
+                if i == 0 {
+                    load_items.write_fmt(format_args!("({})i", ty))?;
+                    continue;
+                }
+                let expr = if self.data == DataSource::Json {
+                    format!("ConfigJson.Read<{}>(row[\"{}\"])", ty, column.ident)
+                } else {
+                    read_expr(&shape_of(&column.ty), &ty)
+                };
+                // LString ids are looked up by the constructor
+                if lstring_column(column).is_some() {
+                    load_items.write_fmt(format_args!(", {}", expr))?;
+                } else {
+                    load_items.write_fmt(format_args!(", {}", collections::freeze(&ty, &expr)))?;
+                }
+            }
+            load_items.write_fmt(format_args!("));{}", end))?;
+            format(tab_nums + 3, &mut load_items)?;
+            load_items.write_fmt(format_args!("OnItemCreated(_dataArray[i]);{}", end))?;
+            format(tab_nums + 2, &mut load_items)?;
+            load_items.write_fmt(format_args!("}}{}", end))?;
+            format(tab_nums + 1, &mut load_items)?;
+            load_items.write("}".as_bytes())?;
+            init_items.push(format!("{}LoadItems();", "\t".repeat(tab_nums as usize + 2)));
+        }
+
+        // enum-flags
+        let mut enum_flags = Vec::<u8>::default();
+        for (k, _) in enumflags.iter() {
+            let ref_type = naming::enum_type("", &format!("{}ReferencedType", k));
+            format(tab_nums + 1, &mut enum_flags)?;
+            enum_flags.write_fmt(format_args!("public static int Get{}Bonus(int key, {} property){}", k, ref_type, end))?;
+            format(tab_nums + 1, &mut enum_flags)?;
+            enum_flags.write("{".as_bytes())?;
+            enum_flags.write(end.as_bytes())?;
+            format(tab_nums + 2, &mut enum_flags)?;
+            enum_flags.write_fmt(format_args!("return Instance._dataArray[key].Get{}BonusInt(property);{}", k, end))?;
+            format(tab_nums + 1, &mut enum_flags)?;
+            enum_flags.write("}".as_bytes())?;
+            enum_flags.write(end.as_bytes())?;
+            enum_flags.write(end.as_bytes())?;
+
+            // sums over the lists of keys
+            for (keys, count) in [("short[]", "Length"), ("List<short>", "Count"), ("int[]", "Length"), ("List<int>", "Count")] {
+                format(tab_nums + 1, &mut enum_flags)?;
+                enum_flags.write_fmt(format_args!("public static int Get{}Bonus({} keys, {} property){}", k, keys, ref_type, end))?;
+                format(tab_nums + 1, &mut enum_flags)?;
+                enum_flags.write("{".as_bytes())?;
+                enum_flags.write(end.as_bytes())?;
+                format(tab_nums + 2, &mut enum_flags)?;
+                enum_flags.write("int sum = 0;".as_bytes())?;
+                enum_flags.write(end.as_bytes())?;
+                format(tab_nums + 2, &mut enum_flags)?;
+                enum_flags.write_fmt(format_args!("for (int i = 0, count = keys.{}; i < count; ++i){}", count, end))?;
+                format(tab_nums + 3, &mut enum_flags)?;
+                enum_flags.write_fmt(format_args!("sum += Instance._dataArray[keys[i]].Get{}BonusInt(property);{}", k, end))?;
+                format(tab_nums + 2, &mut enum_flags)?;
+                enum_flags.write("return sum;".as_bytes())?;
+                enum_flags.write(end.as_bytes())?;
+                format(tab_nums + 1, &mut enum_flags)?;
+                enum_flags.write("}".as_bytes())?;
+                enum_flags.write(end.as_bytes())?;
+                enum_flags.write(end.as_bytes())?;
+            }
+        }
+        // the line of the placeholder ends the last one
+        enum_flags.truncate(enum_flags.len().saturating_sub(end.len()));
+
+        let required_fields = columns.iter().filter(|v| v.default.is_none())
+            .map(|v| format!("{}\"{}\",", "\t".repeat(tab_nums as usize + 2), v.ident))
+            .collect::<Vec<String>>();
+
+        let lines_str = lines.to_string();
+        let class_name = naming.table_class(name);
+        let text = template::render("base_class.tpl", &[
+            ("Name", name),
+            ("ClassName", &class_name),
+            ("ItemName", &item_name),
+            ("IdType", id_type),
+            ("Lines", &lines_str),
+            ("DefKey", &String::from_utf8_lossy(&def_key)),
+            ("CreateItems", &String::from_utf8_lossy(&create_items)),
+            ("LoadItems", &String::from_utf8_lossy(&load_items)),
+            ("InitItems", &init_items.join(end)),
+            ("EnumFlags", &String::from_utf8_lossy(&enum_flags)),
+            ("RequiredFields", &required_fields.join(end)),
+        ])?;
+        stream.write(text.as_bytes())?;
+        Ok(())
+    }
+}
+
+// ConfigBinary.Reader call of one value, ty is the C# type of the shape
+fn read_expr(shape: &Shape, ty: &str) -> String {
+    match shape {
+        Shape::Bool => String::from("reader.ReadBool()"),
+        Shape::SByte => String::from("reader.ReadSByte()"),
+        Shape::Byte => String::from("reader.ReadByte()"),
+        Shape::Short => String::from("reader.ReadShort()"),
+        Shape::UShort => String::from("reader.ReadUShort()"),
+        Shape::Int => String::from("reader.ReadInt()"),
+        Shape::UInt => String::from("reader.ReadUInt()"),
+        Shape::Float => String::from("reader.ReadFloat()"),
+        Shape::Double => String::from("reader.ReadDouble()"),
+        Shape::String => String::from("reader.ReadString()"),
+        Shape::ShortList => String::from("reader.ReadShortList()"),
+        Shape::Enum => format!("({})reader.ReadInt()", ty),
+        Shape::Custom => format!("reader.ReadCustom<{}>()", ty),
+        Shape::Array(inner) => {
+            let inner_ty = ty.strip_suffix("[]").unwrap_or(ty);
+            format!("reader.ReadArray(() => {})", read_expr(inner, inner_ty))
+        }
+        Shape::List(inner) => {
+            format!("reader.ReadList(() => {})", read_expr(inner, &generic_args(ty)[0]))
+        }
+        Shape::Tuple(shapes) | Shape::ValueTuple(shapes) => {
+            let class = if let Shape::Tuple(_) = shape { "Tuple" } else { "ValueTuple" };
+            let args = shapes.iter().zip(generic_args(ty).iter()).map(|(s, t)| read_expr(s, t)).collect::<Vec<String>>();
+            format!("(reader.ReadBool() ? {}.Create({}) : default({}))", class, args.join(", "), ty)
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::defs::CS_COLLECTIONS;
+use std::io::{Error, ErrorKind, Result};
+
+// what the list and array columns of the generated classes are
+#[derive(Clone, Copy, PartialEq)]
+pub enum Collections {
+    Mutable,    // List<T> and T[] as parsed
+    ReadOnly,   // IReadOnlyList<T> holding a ReadOnlyCollection<T>
+    Immutable,  // ImmutableArray<T>, System.Collections.Immutable
+}
+
+pub fn mode() -> Collections {
+    match unsafe { CS_COLLECTIONS } {
+        "readonly" => Collections::ReadOnly,
+        "immutable" => Collections::Immutable,
+        _ => Collections::Mutable,
+    }
+}
+
+pub fn check() -> Result<()> {
+    match unsafe { CS_COLLECTIONS } {
+        "mutable" | "readonly" | "immutable" => Ok(()),
+        v => Err(Error::new(ErrorKind::InvalidInput, format!("unknown cs collections: {}, expected mutable, readonly or immutable", v))),
+    }
+}
+
+// the usings the converted fields need besides the ones of the file
+pub fn usings() -> &'static [&'static str] {
+    match mode() {
+        Collections::Mutable => &[],
+        Collections::ReadOnly => &["System.Linq"],
+        Collections::Immutable => &["System.Linq", "System.Collections.Immutable"],
+    }
+}
+
+// List<T> and T[] -> T
+fn element(ty: &str) -> Option<&str> {
+    if let Some(v) = ty.strip_prefix("List<").and_then(|v| v.strip_suffix('>')) {
+        Some(v)
+    } else {
+        ty.strip_suffix("[]")
+    }
+}
+
+// List<List<int>> -> IReadOnlyList<IReadOnlyList<int>> or ImmutableArray<ImmutableArray<int>>, the rest as it is
+pub fn field_type(ty: &str) -> String {
+    match (mode(), element(ty)) {
+        (Collections::ReadOnly, Some(v)) => format!("IReadOnlyList<{}>", field_type(v)),
+        (Collections::Immutable, Some(v)) => format!("ImmutableArray<{}>", field_type(v)),
+        _ => String::from(ty),
+    }
+}
+
+// literal of a list or array of type ty, items are the literals of the elements
+pub fn literal(ty: &str, items: &str) -> String {
+    let elem = element(ty).map(field_type).unwrap_or_default();
+    match mode() {
+        Collections::Mutable => format!("new {}{{{}}}", ty, items),
+        Collections::ReadOnly => format!("Array.AsReadOnly(new {}[]{{{}}})", elem, items),
+        Collections::Immutable => format!("ImmutableArray.Create<{}>({})", elem, items),
+    }
+}
+
+// the empty cell of a list or array, ImmutableArray is a struct
+pub fn none() -> &'static str {
+    if mode() == Collections::Immutable { "default" } else { "null" }
+}
+
+// expr of type ty as field_type(ty), the nested lists are converted as well. for the rows which are not literals
+pub fn freeze(ty: &str, expr: &str) -> String {
+    freeze_at(ty, expr, 0)
+}
+
+fn freeze_at(ty: &str, expr: &str, depth: usize) -> String {
+    let inner = match element(ty) {
+        Some(v) if mode() != Collections::Mutable => v,
+        _ => return String::from(expr),
+    };
+    let items = if element(inner).is_some() {
+        let var = format!("v{}", depth);
+        format!("{}?.Select({} => {})", expr, var, freeze_at(inner, &var, depth + 1))
+    } else {
+        format!("{}?", expr)
+    };
+    match mode() {
+        Collections::Immutable => format!("{}.ToImmutableArray() ?? default", items),
+        _ => format!("{}.ToList().AsReadOnly()", items),
+    }
+}
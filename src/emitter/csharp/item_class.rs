@@ -0,0 +1,250 @@
+use crate::parser::cell_value::NoneValue;
+use crate::parser::{CellValue, Column, TableModel, enum_ref, bm_search::bm_search};
+use super::{CodeGenerator, collections, template};
+use crate::naming;
+use std::io::{Write, Result};
+
+pub struct ItemClass<'a> {
+    pub model: &'a TableModel
+}
+
+impl<'a> CodeGenerator for ItemClass<'a> {
+    fn gen_code<W: Write + ?Sized>(&self, end: &'static str, tab_nums: i32, stream: &mut W) -> Result<()> {
+        let format = |n: i32, stream: &mut Vec<u8>| -> Result<()> {
+            for _ in 0..n {
+                stream.write("\t".as_bytes())?;
+            }
+            Ok(())
+        };
+
+        let comment = |content: &str, stream: &mut Vec<u8>| -> Result<()> {
+            format(tab_nums + 1, stream)?;
+            stream.write("/// <summary>".as_bytes())?;
+            stream.write(end.as_bytes())?;
+            format(tab_nums + 1, stream)?;
+            stream.write("/// ".as_bytes())?;
+            stream.write(content.as_bytes())?;
+            stream.write(end.as_bytes())?;
+            format(tab_nums + 1, stream)?;
+            stream.write("/// </summary>".as_bytes())?;
+            stream.write(end.as_bytes())?;
+            Ok(())
+        };
+
+        let name = &self.model.name;
+        let columns = &self.model.columns;
+        let enumflags = &self.model.enumflags;
+        let item_name = naming::of(name).item_class(name);
+
+        // a blank line after every field, the line of the placeholder ends the last one
+        let mut fields = Vec::<u8>::default();
+        for column in columns.iter() {
+            if let Some(item_comment) = &column.comment {
+                comment(item_comment, &mut fields)?;
+            }
+
+            format(tab_nums + 1, &mut fields)?;
+            fields.write("public readonly ".as_bytes())?;
+            if column.ty.as_str() == "enum" {
+                fields.write(naming::enum_type(name, &column.ident).as_bytes())?;
+            } else {
+                fields.write(collections::field_type(&replace_enum(&replace_lstring(&column.ty), name)).as_bytes())?;
+            }
+            fields.write_fmt(format_args!(" {};{}{}", column.ident, end, end))?;
+        }
+        fields.truncate(fields.len().saturating_sub(end.len()));
+
+        // construct_0
+        let mut params = Vec::<String>::with_capacity(columns.len());
+        let mut assigns = Vec::<String>::with_capacity(columns.len());
+        for (count, column) in columns.iter().enumerate() {
+            if lstring_column(column).is_some() {
+                params.push(format!("{} arg{}", param_type(column, name), count));
+            } else {
+                params.push(format!("{} arg{}", collections::field_type(&param_type(column, name)), count));
+            }
+
+            // with args, process LString
+            let mut assign = format!("{}{}", "\t".repeat(tab_nums as usize + 2), column.ident);
+            if !column.values.is_empty() {
+                let arg = format!("arg{}", count);
+                if let Some(tpl) = lstring_column(column) {
+                    let text = template::render(tpl, &[("Name", name), ("Arg", &arg)])?;
+                    assign.push_str(&format!(" = {}", collections::freeze(&replace_lstring(&column.ty), &text)));
+                } else {
+                    assign.push_str(&format!(" = {}", arg));
+                }
+            }
+            assign.push(';');
+            assigns.push(assign);
+        }
+
+        // construct_1
+        let mut defaults = Vec::<String>::with_capacity(columns.len());
+        for column in columns.iter() {
+            let mut line = Vec::<u8>::default();
+            format(tab_nums + 2, &mut line)?;
+            line.write(column.ident.as_bytes())?;
+            let cell_ident = &column.values;
+
+            if let Some(val) = &column.default {
+                line.write(" = ".as_bytes())?;
+
+                if !cell_ident.is_empty() {
+                    if let Some(tpl) = lstring_template(&cell_ident[0]) {
+                        let text = template::render(tpl, &[("Name", name), ("Arg", "default")])?;
+                        line.write(collections::freeze(&replace_lstring(&column.ty), &text).as_bytes())?;
+                    } else {
+                        val.gen_code(&mut line)?;
+                    }
+                }
+
+                line.write(";".as_bytes())?;
+            } else {
+                line.write(" = default;".as_bytes())?;
+            }
+            defaults.push(String::from_utf8_lossy(&line).into_owned());
+        }
+
+        // enum-refs, a blank line before each
+        let mut enum_flags = Vec::<u8>::default();
+        for (k, arr) in enumflags.iter() {
+            enum_flags.write(end.as_bytes())?;
+            format(tab_nums + 1, &mut enum_flags)?;
+            let ref_type = naming::enum_type("", &format!("{}ReferencedType", k));
+            enum_flags.write_fmt(format_args!("public int Get{}BonusInt({} key){}", k, ref_type, end))?;
+            format(tab_nums + 1, &mut enum_flags)?;
+            enum_flags.write("{".as_bytes())?;
+            enum_flags.write(end.as_bytes())?;
+            format(tab_nums + 2, &mut enum_flags)?;
+            enum_flags.write_fmt(format_args!("switch (key){}", end))?;
+            format(tab_nums + 2, &mut enum_flags)?;
+            enum_flags.write("{".as_bytes())?;
+            enum_flags.write(end.as_bytes())?;
+
+            for v in arr {
+                format(tab_nums + 3, &mut enum_flags)?;
+                enum_flags.write_fmt(format_args!("case {}.{}:return {};{}", ref_type, v, v, end))?;
+            }
+
+            format(tab_nums + 2, &mut enum_flags)?;
+            enum_flags.write("}".as_bytes())?;
+            enum_flags.write(end.as_bytes())?;
+            format(tab_nums + 2, &mut enum_flags)?;
+            enum_flags.write("return 0;".as_bytes())?;
+            enum_flags.write(end.as_bytes())?;
+            format(tab_nums + 1, &mut enum_flags)?;
+            enum_flags.write("}".as_bytes())?;
+            enum_flags.write(end.as_bytes())?;
+        }
+        enum_flags.truncate(enum_flags.len().saturating_sub(end.len()));
+
+        let text = template::render("item_class.tpl", &[
+            ("Name", name),
+            ("ItemName", &item_name),
+            ("Fields", &String::from_utf8_lossy(&fields)),
+            ("Params", &params.join(",")),
+            ("Assigns", &assigns.join(end)),
+            ("Defaults", &defaults.join(end)),
+            ("EnumFlags", &String::from_utf8_lossy(&enum_flags)),
+        ])?;
+        stream.write(text.as_bytes())?;
+        Ok(())
+    }
+}
+
+// LStrings are looked up by id in the constructors
+fn lstring_template(v: &CellValue) -> Option<&'static str> {
+    if v.is_lstring() {
+        Some("lstring.tpl")
+    } else if v.is_lstring_arr() {
+        Some("lstring_list.tpl")
+    } else {
+        None
+    }
+}
+
+// LStrings of the column, empty cells included, the constructor looks them up
+pub(super) fn lstring_column(column: &Column) -> Option<&'static str> {
+    match column.values.first().map(|v| &**v) {
+        Some(CellValue::DNone(NoneValue(ref v))) => lstring_template(&CellValue::get_type(v)),
+        Some(v) => lstring_template(v),
+        None => None,
+    }
+}
+
+// type of the constructor parameter, LStrings are passed as ids
+pub(crate) fn param_type(column: &Column, name: &str) -> String {
+    let (item_identify, item_type) = (&column.ident, &column.ty);
+    let cell_ident = &column.values;
+    if cell_ident.is_empty() {
+        String::default()
+    } else if cell_ident[0].is_lstring() {
+        String::from("int")
+    } else if cell_ident[0].is_lstring_arr() {
+        String::from("int[]")
+    } else if cell_ident[0].is_enum() && item_type.as_str() == "enum" {
+        naming::enum_type(name, item_identify)
+    } else if let CellValue::DNone(NoneValue(ref v)) = *cell_ident[0] {
+        let ty = CellValue::get_type(v);
+        if ty.is_lstring() {
+            String::from("int")
+        } else if ty.is_lstring_arr() {
+            String::from("int[]")
+        } else if item_type.as_str() == "enum" {
+            naming::enum_type(name, item_identify)
+        } else {
+            replace_enum(item_type, name)
+        }
+    } else {
+        replace_enum(item_type, name)
+    }
+}
+
+fn replace_lstring(val: &str) -> String {
+    let mut ret = String::with_capacity(val.len());
+    let indexs_1 = bm_search(val, "LString");
+    let indexs_2 = bm_search(val, "Lstring");
+
+    if indexs_1.is_empty() && indexs_2.is_empty() {
+        return String::from(val);
+    } else if indexs_1.is_empty() {
+        if indexs_2[0] == 0 {
+            ret.push_str("string");
+            ret.push_str(&val[7..]);
+        } else {
+            ret.push_str(&val[..indexs_2[0]]);
+            ret.push('s');
+            ret.push_str(&val[indexs_2[0]+2..]);
+        }
+    } else if indexs_2.is_empty() {
+        if indexs_1[0] == 0 {
+            ret.push_str("string");
+            ret.push_str(&val[7..]);
+        } else {
+            ret.push_str(&val[..indexs_1[0]]);
+            ret.push('s');
+            ret.push_str(&val[indexs_1[0]+2..]);
+        }
+    } else {
+        unreachable!()
+    }
+    ret
+}
+
+// enum:Name -> E{base_name}Name, enum:Other.Name -> EOtherName, by the enum naming of the owner
+fn replace_enum(val: &str, base_name: &str) -> String {
+    let mut ret = String::with_capacity(val.len());
+    let mut rest = val;
+
+    while let Some(idx) = rest.find("enum:") {
+        ret.push_str(&rest[..idx]);
+        let tail = &rest[idx..];
+        let len = tail.find(|c: char| c == '>' || c == '[' || c == ',' || c == ' ').unwrap_or(tail.len());
+        let (_, owner, name) = enum_ref(&tail[..len], base_name);
+        ret.push_str(&naming::enum_type(owner, name));
+        rest = &tail[len..];
+    }
+    ret.push_str(rest);
+    ret
+}
\ No newline at end of file
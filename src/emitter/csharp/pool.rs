@@ -0,0 +1,142 @@
+use crate::parser::{CellValue, TableModel};
+use super::collections::{self, Collections};
+use super::item_class::param_type;
+use std::collections::HashMap;
+use std::io::Result;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// bytes saved by the pools of every table of the build
+static SAVED: AtomicUsize = AtomicUsize::new(0);
+
+// the cell literals more rows than one share, written once as private static readonly fields of the table class.
+// only the ones no row can change: tuples, lists and arrays when they are read-only or immutable
+#[derive(Default)]
+pub struct LiteralPool {
+    // <(type, literal), field>
+    names: HashMap<(String, String), String>,
+    // (type, field, literal) in the order of the first use
+    fields: Vec<(String, String, String)>,
+}
+
+impl LiteralPool {
+    pub fn new(model: &TableModel, tab_nums: i32, end: &str) -> Result<LiteralPool> {
+        let columns = &model.columns;
+        // <(type, literal), uses> and the order of the first use
+        let mut uses = HashMap::<(String, String), usize>::default();
+        let mut order = Vec::<(String, String)>::default();
+        for row in 0..model.lines {
+            for column in columns.iter().skip(1) {
+                let v = column.value(row);
+                if !poolable(v) {
+                    continue;
+                }
+                let key = (collections::field_type(&param_type(column, &model.name)), literal(v)?);
+                let count = uses.entry(key.clone()).or_default();
+                if *count == 0 {
+                    order.push(key);
+                }
+                *count += 1;
+            }
+        }
+
+        let mut ret = LiteralPool::default();
+        let (mut inline, mut pooled) = (0, 0);
+        for key in order {
+            let count = uses[&key];
+            let name = format!("_pool{}", ret.fields.len());
+            let decl = field(&key.0, &name, &key.1, tab_nums, end);
+            // a field longer than the copies it replaces is not worth it
+            if count < 2 || name.len() * count + decl.len() >= key.1.len() * count {
+                continue;
+            }
+            inline += key.1.len() * count;
+            pooled += name.len() * count + decl.len();
+            ret.names.insert(key.clone(), name.clone());
+            ret.fields.push((key.0, name, key.1));
+        }
+
+        if !ret.fields.is_empty() {
+            // and the empty line before the fields
+            let saved = inline.saturating_sub(pooled + end.len());
+            SAVED.fetch_add(saved, Ordering::Relaxed);
+            println!("{}: {} literals pooled, {} bytes saved", model.name, ret.fields.len(), saved);
+        }
+        Ok(ret)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    // the literal of the cell, or the field it is pooled in
+    pub fn get(&self, v: &CellValue, ty: &str) -> Result<String> {
+        let text = literal(v)?;
+        if poolable(v) {
+            if let Some(name) = self.names.get(&(String::from(ty), text.clone())) {
+                return Ok(name.clone());
+            }
+        }
+        Ok(text)
+    }
+
+    // the field declarations, each ending with end
+    pub fn gen_fields(&self, tab_nums: i32, end: &str) -> String {
+        self.fields.iter().map(|(ty, name, text)| field(ty, name, text, tab_nums, end)).collect()
+    }
+}
+
+// bytes saved by the pools of the build
+pub fn saved() -> usize {
+    SAVED.load(Ordering::Relaxed)
+}
+
+fn poolable(v: &CellValue) -> bool {
+    match v {
+        CellValue::DTuple(_) | CellValue::DValueTuple(_) => true,
+        CellValue::DList(_) | CellValue::DArray(_) => collections::mode() != Collections::Mutable && !v.is_lstring_arr(),
+        _ => false,
+    }
+}
+
+fn literal(v: &CellValue) -> Result<String> {
+    let mut text = Vec::<u8>::default();
+    v.gen_code(&mut text)?;
+    Ok(String::from_utf8_lossy(&text).into_owned())
+}
+
+fn field(ty: &str, name: &str, text: &str, tab_nums: i32, end: &str) -> String {
+    format!("{}private static readonly {} {} = {};{}", "\t".repeat(tab_nums as usize + 1), ty, name, text, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Column;
+    use crate::parser::cell_value::{IntValue, ValueTupleValue};
+
+    fn pair(a: i32, b: i32) -> CellValue {
+        CellValue::DValueTuple(ValueTupleValue(vec![CellValue::DInt(IntValue(a)), CellValue::DInt(IntValue(b))]))
+    }
+
+    #[test]
+    fn shared_literals_are_pooled_once() {
+        let mut ranges = (0..10).map(|_| pair(100, 200)).collect::<Vec<CellValue>>();
+        ranges.push(pair(1, 2));
+        let model = TableModel {
+            name: String::from("Pooled"),
+            lines: ranges.len(),
+            columns: vec![
+                Column::new("TemplateId", "int", (0..ranges.len() as i32).map(|v| CellValue::DInt(IntValue(v))).collect()),
+                Column::new("Range", "ValueTuple<int,int>", ranges),
+            ],
+            ..Default::default()
+        };
+
+        let pool = LiteralPool::new(&model, 1, "\n").unwrap();
+        let ty = collections::field_type("ValueTuple<int,int>");
+        assert_eq!(pool.get(&pair(100, 200), &ty).unwrap(), "_pool0");
+        // used once, not worth a field
+        assert_eq!(pool.get(&pair(1, 2), &ty).unwrap(), literal(&pair(1, 2)).unwrap());
+        assert_eq!(pool.gen_fields(1, "\n"), format!("\t\tprivate static readonly {} _pool0 = {};\n", ty, literal(&pair(100, 200)).unwrap()));
+    }
+}
@@ -0,0 +1,86 @@
+use crate::defs::{CS_TEMPLATE_DIR, LINE_END_FLAG};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, Write, Result};
+use std::path::Path;
+
+// name and built-in text of every template, a file of the same name in the template dir replaces it
+pub const TEMPLATES: [(&str, &str); 6] = [
+    ("file.tpl", include_str!("templates/file.tpl")),
+    ("item_class.tpl", include_str!("templates/item_class.tpl")),
+    ("base_class.tpl", include_str!("templates/base_class.tpl")),
+    ("lstring.tpl", include_str!("templates/lstring.tpl")),
+    ("lstring_list.tpl", include_str!("templates/lstring_list.tpl")),
+    ("global_config.tpl", include_str!("templates/global_config.tpl")),
+];
+
+lazy_static! (
+    static ref LOADED: DashMap<&'static str, String> = DashMap::default();
+);
+
+// {{Var}} is replaced by its value, a line of only an empty {{Var}} is dropped.
+// the final line end of the template is not part of it, lines end with LINE_END_FLAG
+pub fn render(name: &'static str, vars: &[(&str, &str)]) -> Result<String> {
+    let text = load(name)?;
+    let mut lines = Vec::<String>::default();
+    for line in text.split('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with("{{") && trimmed.ends_with("}}") && trimmed.matches("{{").count() == 1 {
+            if lookup(name, &trimmed[2..trimmed.len() - 2], vars)?.is_empty() {
+                continue;
+            }
+        }
+
+        let mut ret = String::with_capacity(line.len());
+        let mut rest = line;
+        while let Some(start) = rest.find("{{") {
+            let len = rest[start..].find("}}").ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("unclosed {{{{ in template {}", name)))?;
+            ret.push_str(&rest[..start]);
+            ret.push_str(lookup(name, &rest[start + 2..start + len], vars)?);
+            rest = &rest[start + len + 2..];
+        }
+        ret.push_str(rest);
+        lines.push(ret);
+    }
+    Ok(lines.join(LINE_END_FLAG))
+}
+
+// the built-in templates into dir, to start from
+pub fn write_defaults<P: AsRef<Path>>(dir: P) -> Result<()> {
+    fs::create_dir_all(&dir)?;
+    for (name, text) in TEMPLATES.iter() {
+        let mut file = File::create(dir.as_ref().join(name))?;
+        file.write(text.as_bytes())?;
+        file.flush()?;
+    }
+    Ok(())
+}
+
+fn lookup<'a>(name: &str, var: &str, vars: &[(&str, &'a str)]) -> Result<&'a str> {
+    let var = var.trim();
+    vars.iter().find(|v| v.0 == var).map(|v| v.1).ok_or_else(|| {
+        let known = vars.iter().map(|v| v.0).collect::<Vec<&str>>();
+        Error::new(ErrorKind::InvalidData, format!("unknown variable {} in template {}, expected one of {}", var, name, known.join(", ")))
+    })
+}
+
+fn load(name: &'static str) -> Result<String> {
+    if let Some(v) = LOADED.get(name) {
+        return Ok(v.clone());
+    }
+
+    let dir = unsafe { CS_TEMPLATE_DIR };
+    let path = Path::new(dir).join(name);
+    let mut text = if !dir.is_empty() && path.exists() {
+        fs::read_to_string(path)?
+    } else {
+        String::from(TEMPLATES.iter().find(|v| v.0 == name).map_or("", |v| v.1))
+    };
+    text = text.replace('\r', "");
+    if text.ends_with('\n') {
+        text.pop();
+    }
+    LOADED.insert(name, text.clone());
+    Ok(text)
+}
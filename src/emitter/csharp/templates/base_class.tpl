@@ -0,0 +1,121 @@
+	[Serializable]
+	public partial class {{ClassName}} : IEnumerable<{{ItemName}}>, IConfigData
+	{
+		public static {{ClassName}} Instance = new {{ClassName}}();
+{{DefKey}}
+		private readonly Dictionary<string, int> _refNameMap = new Dictionary<string, int>();
+		private List<{{ItemName}}> _dataArray = null;
+{{CreateItems}}
+{{LoadItems}}
+
+		public void Init()
+		{
+			_refNameMap.Clear();
+			_refNameMap.Load("{{Name}}");
+			_extraDataMap.Clear();
+			_dataArray = new List<{{ItemName}}>( {{Lines}} ) {
+			};
+{{InitItems}}
+			OnInit();
+		}
+
+		/// <summary>
+		/// called by Init for every row after it is created, implement it in a partial class of the same name
+		/// </summary>
+		partial void OnItemCreated({{ItemName}} item);
+
+		/// <summary>
+		/// called at the end of Init when every row is there, implement it in a partial class of the same name
+		/// </summary>
+		partial void OnInit();
+
+		public int GetItemId(string refName)
+		{
+			if (_refNameMap.TryGetValue(refName, out var id))
+				return id;
+			throw new Exception($"{refName} not found.");
+		}
+
+		private readonly Dictionary<int, {{ItemName}}> _extraDataMap = new Dictionary<int, {{ItemName}}>();
+
+		public int AddExtraItem(string identifier, string refName, object configItem)
+		{
+			var item = ({{ItemName}})configItem;
+			var id = (int) item.TemplateId;
+			if (id < _dataArray.Count)
+				throw new Exception($"{{Name}} template id {item.TemplateId} created by {identifier} already exist.");
+			if (_extraDataMap.ContainsKey(id))
+				throw new Exception($"{{Name}} extra template id {item.TemplateId} created by {identifier} already exist.");
+			if (_refNameMap.TryGetValue(refName, out var refId))
+				throw new Exception($"{{Name}} template reference name {refName}(id = {item.TemplateId}) created by {identifier} already exist with templateId {refId}).");
+			_refNameMap.Add(refName, id);
+			_extraDataMap.Add(id, item);
+			return id;
+		}
+
+		public {{ItemName}} this[{{IdType}} id] => GetItem(id);
+
+		public {{ItemName}} this[int id] => GetItem(({{IdType}})id);
+
+		public {{ItemName}} GetItem({{IdType}} id)
+		{
+			if (id < 0) return null;
+			if (id < _dataArray.Count) return _dataArray[(int)id];
+			if (_extraDataMap.TryGetValue((int) id, out var item)) return item;
+			// 预期为有效 Id 但仍然访问不到数据时
+			GameData.Utilities.AdaptableLog.TagWarning(GetType().FullName, $"index {id} is not in range [0, {_dataArray.Count}) and is not defined in _extraDataMap (count: {_extraDataMap.Count})");
+			return null;
+		}
+
+		public {{ItemName}} this[string refName] => this[_refNameMap[refName]];
+
+{{EnumFlags}}
+		private readonly HashSet<string> RequiredFields = new HashSet<string>()
+		{
+{{RequiredFields}}
+		};
+
+		public List<{{IdType}}> GetAllKeys()
+		{
+			return (from item in _dataArray where null != item select item.TemplateId).ToList();
+		}
+
+		public int Count => _dataArray.Count;
+		public int CountWithExtra => Count + _extraDataMap.Count;
+
+		public void Iterate(Func<{{ItemName}},bool> iterateFunc)
+		{
+			if(null == iterateFunc)
+				return;
+			foreach({{ItemName}} item in _dataArray)
+			{
+				if(null == item)
+					continue;
+				if(!iterateFunc(item))
+					break;
+			}
+			foreach({{ItemName}} item in _extraDataMap.Values)
+			{
+				if(null == item)
+					continue;
+				if(!iterateFunc(item))
+					break;
+			}
+		}
+
+		IEnumerator<{{ItemName}}> IEnumerable<{{ItemName}}>.GetEnumerator()
+		{
+			foreach (var item in _dataArray)
+				yield return item;
+			foreach (var item in _extraDataMap.Values)
+				yield return item;
+		}
+
+		IEnumerator IEnumerable.GetEnumerator()
+		{
+			foreach (var item in _dataArray)
+				yield return item;
+			foreach (var item in _extraDataMap.Values)
+				yield return item;
+		}
+	}
@@ -0,0 +1,14 @@
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// This File is generated by the program, DO NOT EDIT MANUALLY!
+// 此文件由程序生成, 切勿手动编辑!
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+{{Usings}}
+
+#pragma warning disable 1591
+
+namespace {{Namespace}}
+{
+{{ItemClass}}
+
+{{BaseClass}}
+}
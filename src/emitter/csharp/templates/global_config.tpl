@@ -0,0 +1,22 @@
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+// This File is generated by the program, DO NOT EDIT MANUALLY!
+// 此文件由程序生成, 切勿手动编辑!
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+{{Usings}}
+
+#pragma warning disable 1591
+
+[System.Serializable]
+public class {{Name}} : IConfigData
+{
+	public int GetItemId(string refName) => throw new System.NotImplementedException();
+	public int AddExtraItem(string identifier, string refName, object configItem) => throw new System.NotImplementedException();
+	public static {{Name}} Instance = new {{Name}}();
+
+{{Fields}}
+
+	public void Init()
+	{
+{{Inits}}
+	}
+}
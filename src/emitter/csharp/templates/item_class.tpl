@@ -0,0 +1,22 @@
+	[Serializable]
+	public partial class {{ItemName}}
+	{
+{{Fields}}
+		public {{ItemName}}({{Params}})
+		{
+{{Assigns}}
+			OnConstructed();
+		}
+
+		public {{ItemName}}()
+		{
+{{Defaults}}
+			OnConstructed();
+		}
+
+		/// <summary>
+		/// called at the end of both constructors, implement it in a partial class of the same name
+		/// </summary>
+		partial void OnConstructed();
+{{EnumFlags}}
+	}
@@ -0,0 +1 @@
+LocalStringManager.GetConfig("{{Name}}_language", {{Arg}})
@@ -0,0 +1 @@
+LocalStringManager.ConvertConfigList("{{Name}}_language", {{Arg}})
@@ -0,0 +1,549 @@
+use crate::parser::cell_value::*;
+use crate::naming;
+use super::collections;
+use std::io::{Write, Result};
+
+macro_rules! get_basic_type_string {
+    ($self:ident, $stream:ident, $($enum:ident::$variant:ident),+) => {
+        match $self {
+            $( $enum::$variant(v) => v.ty($stream) ),+,
+            _ => Ok(())
+        }
+    };
+}
+
+macro_rules! gen_code {
+    ($self:ident, $stream:ident, $($enum:ident::$variant:ident),+) => {
+        match $self {
+            $( $enum::$variant(v) => v.value($stream) ),+,
+        }
+    };
+}
+
+macro_rules! write_value_to_stream {
+    ($vv:ident, $stream:ident, $($enum:ident::$variant:ident),+) => {
+        match $vv {
+            $( $enum::$variant(v) => { v.value($stream)?; } ),+,
+            _ => { $stream.write("".as_bytes())?; }
+        }
+    };
+}
+
+// C# literals of the parsed values
+pub trait ValueInfo {
+    // code
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()>;
+    // used by array/list code
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()>;
+}
+
+impl CellValue {
+    pub(crate) fn gen_code<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        gen_code!(
+            self,
+            stream,
+            CellValue::DEnum,
+            CellValue::DBool,
+            CellValue::DByte, 
+            CellValue::DSByte, 
+            CellValue::DInt, 
+            CellValue::DUInt,
+            CellValue::DFloat,
+            CellValue::DDouble,
+            CellValue::DShort, 
+            CellValue::DUShort, 
+            CellValue::DString,
+            CellValue::DLString,
+            CellValue::DCustom,
+            CellValue::DShortList,
+            CellValue::DTuple,
+            CellValue::DValueTuple,
+            CellValue::DArray,
+            CellValue::DList,
+            CellValue::DNone,
+            CellValue::DError
+        )
+    }
+
+    pub(crate) fn get_basic_type_string<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        get_basic_type_string!(
+            self,
+            stream,
+            CellValue::DEnum,
+            CellValue::DBool, 
+            CellValue::DByte, 
+            CellValue::DSByte, 
+            CellValue::DInt, 
+            CellValue::DUInt, 
+            CellValue::DShort, 
+            CellValue::DUShort,
+            CellValue::DFloat,
+            CellValue::DDouble,
+            CellValue::DString,
+            CellValue::DLString,
+            CellValue::DShortList,
+            CellValue::DCustom,
+            CellValue::DTuple,
+            CellValue::DValueTuple
+        )
+    }
+}
+
+// list and array literals by the collections option, LString ids stay an array for the constructor to look up
+fn list_literal(first: &CellValue, ty: &[u8], items: &[u8]) -> String {
+    let (ty, items) = (String::from_utf8_lossy(ty), String::from_utf8_lossy(items));
+    if first.is_lstring() {
+        format!("new {}{{{}}}", ty, items)
+    } else {
+        collections::literal(&ty, &items)
+    }
+}
+
+//----------------------------------impl-------------------------------------------
+
+impl ValueInfo for ErrorValue {
+    fn value<W: Write + ?Sized>(&self, _stream: &mut W) -> Result<()> {
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, _stream: &mut W) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ValueInfo for NoneValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        match CellValue::get_type(&self.0) {
+            CellValue::DBool(_) => { stream.write("false".as_bytes())?; }
+            CellValue::DSByte(_) | CellValue::DLString(_) | CellValue::DInt(_) | CellValue::DShort(_) => { stream.write("-1".as_bytes())?; }
+            CellValue::DArray(_) | CellValue::DList(_) => { stream.write(collections::none().as_bytes())?; }
+            CellValue::DEnum(_) | CellValue::DShortList(_) | CellValue::DTuple(_) | CellValue::DCustom(_) | CellValue::DString(_) => { stream.write("null".as_bytes())?; }
+            CellValue::DDouble(_) | CellValue::DFloat(_) => { stream.write("0.0".as_bytes())?; }
+            CellValue::DUInt(_) | CellValue::DByte(_) | CellValue::DUShort(_) => { stream.write("0".as_bytes())?; }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("none".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for EnumValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        if self.1.is_empty() {
+            stream.write("default".as_bytes())?;
+        } else {
+            stream.write_fmt(format_args!("{}.{}", naming::enum_type(&self.2, &self.0), self.1))?;
+        }
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write(naming::enum_type(&self.2, &self.0).as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for ShortListValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("new ShortList(".as_bytes())?;
+        let mut cnt = 1;
+        for v in self.0.0.iter().skip(1) {
+            if let CellValue::DShort(vv) = v{
+                stream.write(vv.0.to_string().as_bytes())?;
+                if cnt < self.0.0.len()-1 {
+                    stream.write(",".as_bytes())?;
+                }
+            } else {
+                println!("ShortList value format failed");
+            }
+            cnt += 1;
+        }
+        stream.write(")".as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("ShortList".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for BoolValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        if self.0 == false {
+            stream.write("false".as_bytes())?;
+        } else {
+            stream.write("true".as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("bool".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for LStringValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write(self.1.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("int".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for StringValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        if self.0.is_empty() {
+            stream.write("\"\"".as_bytes())?;
+        } else if self.0.as_str() == "\"\"" {
+            stream.write("\"\"".as_bytes())?;
+        } else if self.0.as_str().contains("\"") {
+            stream.write(self.0.as_bytes())?;
+        } else {
+            stream.write("\"".as_bytes())?;
+            stream.write(self.0.as_bytes())?;
+            stream.write("\"".as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("string".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for ShortValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write(self.0.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("short".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for UShortValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write(self.0.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("ushort".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for IntValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write(self.0.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("int".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for UIntValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write(self.0.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("uint".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for FloatValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write_fmt(format_args!("{:E}f", self.0))?;
+        //stream.write(self.0.to_string().as_bytes())?;
+        //stream.write("f".as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("float".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for DoubleValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write_fmt(format_args!("{:E}d", self.0))?;
+        //stream.write(self.0.to_string().as_bytes())?;
+        //stream.write("d".as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("double".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for ByteValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write(self.0.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("byte".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for SByteValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write(self.0.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("sbyte".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for CustomValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("new ".as_bytes())?;
+        stream.write(self.0.to_string().as_bytes())?;
+        stream.write("(".as_bytes())?;
+        for v in self.1.as_str()[1..self.1.len()-1].chars() {
+            match v {
+                '{' => { stream.write("new []{".as_bytes())?; },
+                _ => { stream.write(v.to_string().as_bytes())?; }
+            }
+        }
+        stream.write(")".as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write(self.0.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for ArrayValue {
+    fn value<W: Write + ?Sized>(&self, out: &mut W) -> Result<()> {
+        if self.0.is_empty() {
+            out.write("".as_bytes())?;
+        } else {
+            let mut ty = Vec::<u8>::default();
+            self.ty(&mut ty)?;
+            let stream = &mut Vec::<u8>::default();
+            let mut cnt = 1;
+
+            for v in self.0.iter().skip(1) {
+                write_value_to_stream!(
+                    v,
+                    stream,
+                    CellValue::DEnum,
+                    CellValue::DBool,
+                    CellValue::DByte, 
+                    CellValue::DSByte, 
+                    CellValue::DInt, 
+                    CellValue::DUInt,
+                    CellValue::DFloat,
+                    CellValue::DDouble,
+                    CellValue::DShort, 
+                    CellValue::DUShort, 
+                    CellValue::DString,
+                    CellValue::DLString,
+                    CellValue::DCustom,
+                    CellValue::DTuple,
+                    CellValue::DValueTuple
+                );
+
+                if cnt < self.0.len()-1 {
+                    stream.write(",".as_bytes())?;
+                }
+                cnt += 1;
+            }
+            out.write(list_literal(&self.0[0], &ty, stream).as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        (self.0)[0].get_basic_type_string(stream)?;
+        stream.write("[]".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for ListValue {
+    fn value<W: Write + ?Sized>(&self, out: &mut W) -> Result<()> {
+        if self.0.is_empty() {
+            out.write("".as_bytes())?;
+        } else {
+            let mut ty = Vec::<u8>::default();
+            self.ty(&mut ty)?;
+            let stream = &mut Vec::<u8>::default();
+            let mut cnt = 1;
+
+            for v in self.0.iter().skip(1) {
+                write_value_to_stream!(
+                    v,
+                    stream,
+                    CellValue::DEnum,
+                    CellValue::DBool,
+                    CellValue::DByte, 
+                    CellValue::DSByte, 
+                    CellValue::DInt, 
+                    CellValue::DUInt,
+                    CellValue::DFloat,
+                    CellValue::DDouble,
+                    CellValue::DShort, 
+                    CellValue::DUShort, 
+                    CellValue::DString,
+                    CellValue::DLString,
+                    CellValue::DCustom,
+                    CellValue::DShortList,
+                    CellValue::DTuple,
+                    CellValue::DValueTuple,
+                    CellValue::DArray,
+                    CellValue::DList
+                );
+
+                if cnt < self.0.len()-1 {
+                    stream.write(",".as_bytes())?;
+                }
+                cnt += 1;
+            }
+            out.write(list_literal(&self.0[0], &ty, stream).as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        stream.write("List<".as_bytes())?;
+        let first = self.0.first().unwrap();
+        match first {
+            CellValue::DArray(v) => { v.ty(stream)?; }
+            CellValue::DList(v) => { v.ty(stream)?; }
+            // basic type
+            _ => {
+                first.get_basic_type_string(stream)?;
+            }
+        }
+        stream.write(">".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for TupleValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        let mut cnt = 0;
+        stream.write("new ".as_bytes())?;
+        self.ty(stream)?;
+        stream.write("(".as_bytes())?;
+        for v in self.0.iter() {
+            write_value_to_stream!(
+                v,
+                stream,
+                CellValue::DBool,
+                CellValue::DByte, 
+                CellValue::DSByte, 
+                CellValue::DInt, 
+                CellValue::DUInt,
+                CellValue::DFloat,
+                CellValue::DDouble,
+                CellValue::DShort, 
+                CellValue::DUShort, 
+                CellValue::DString,
+                CellValue::DLString,
+                CellValue::DCustom
+            );
+
+            if cnt < self.0.len()-1 {
+                stream.write(",".as_bytes())?;
+            }
+            cnt += 1;
+        }
+        stream.write(")".as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        let mut cnt = 0;
+        stream.write("Tuple<".as_bytes())?;
+        for v in self.0.iter() {
+            v.get_basic_type_string(stream)?;
+            if cnt < self.0.len() - 1 {
+                stream.write(",".as_bytes())?;
+            }
+            cnt += 1;
+        }
+        stream.write(">".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ValueInfo for ValueTupleValue {
+    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        let mut cnt = 0;
+        stream.write("new ".as_bytes())?;
+        self.ty(stream)?;
+        stream.write("(".as_bytes())?;
+        for v in self.0.iter() {
+            write_value_to_stream!(
+                v,
+                stream,
+                CellValue::DBool,
+                CellValue::DByte, 
+                CellValue::DSByte, 
+                CellValue::DInt, 
+                CellValue::DUInt,
+                CellValue::DFloat,
+                CellValue::DDouble,
+                CellValue::DShort, 
+                CellValue::DUShort, 
+                CellValue::DString,
+                CellValue::DLString,
+                CellValue::DCustom,
+                CellValue::DEnum
+            );
+
+            if cnt < self.0.len()-1 {
+                stream.write(",".as_bytes())?;
+            }
+            cnt += 1;
+        }
+        stream.write(")".as_bytes())?;
+        Ok(())
+    }
+
+    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
+        let mut cnt = 0;
+        stream.write("ValueTuple<".as_bytes())?;
+        for v in self.0.iter() {
+            v.get_basic_type_string(stream)?;
+            if cnt < self.0.len() - 1 {
+                stream.write(",".as_bytes())?;
+            }
+            cnt += 1;
+        }
+        stream.write(">".as_bytes())?;
+        Ok(())
+    }
+}
\ No newline at end of file
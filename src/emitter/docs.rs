@@ -0,0 +1,306 @@
+use crate::defs::{OUTPUT_DOCS_DIR, DOCS_FORMAT, LINE_END_FLAG};
+use crate::naming;
+use crate::parser::{TableModel, EnumDef};
+use super::Emitter;
+use super::csharp::param_type;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, Write, Result};
+
+struct ColumnDoc {
+    ident: String,
+    ty: String,             // C# type
+    comment: String,
+    default: String,        // json text
+    fk: Option<(String, Option<String>)>, // pattern and target table
+}
+
+struct TableDoc {
+    rows: usize,
+    id_type: String,
+    columns: Vec<ColumnDoc>,
+    def_keys: Vec<String>,
+}
+
+struct EnumDoc {
+    owner: String,
+    members: Vec<(String, i32, String)>,
+}
+
+lazy_static! (
+    static ref TABLES: DashMap<String, TableDoc> = DashMap::default();
+    static ref ENUMS: DashMap<String, EnumDoc> = DashMap::default();
+);
+
+// index, {Name} and E{Base}{Name} pages into the docs dir, md or html.
+// the pages are written by finish, the back links need every table of the build
+pub struct DocsEmitter;
+
+impl Emitter for DocsEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        for def in model.enums.iter() {
+            self.emit_enum(def)?;
+        }
+
+        let mut columns = Vec::<ColumnDoc>::default();
+        for column in model.columns.iter() {
+            let mut default = Vec::<u8>::default();
+            if let Some(v) = column.default.as_ref() {
+                super::json::write_value(v, &mut default)?;
+            }
+            columns.push(ColumnDoc {
+                ident: column.ident.to_string(),
+                ty: param_type(column, &model.name),
+                comment: column.comment.as_ref().map(|v| v.to_string()).unwrap_or_default(),
+                default: String::from_utf8_lossy(&default).into_owned(),
+                fk: column.fk.as_ref().map(|v| (format!("*{}", v), column.fk_table().map(String::from))),
+            });
+        }
+
+        let doc = TableDoc {
+            rows: model.lines,
+            id_type: model.id_type.to_string(),
+            columns,
+            def_keys: model.def_keys().iter().map(|v| v.0.to_string()).collect(),
+        };
+        TABLES.insert(model.name.clone(), doc);
+        Ok(())
+    }
+
+    fn emit_enum(&self, def: &EnumDef) -> Result<()> {
+        let doc = EnumDoc {
+            owner: def.owner.clone(),
+            members: def.members.iter().map(|v| (v.0.to_string(), v.1, v.2.to_string())).collect(),
+        };
+        ENUMS.insert(naming::enum_type(&def.owner, &def.name), doc);
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<()> {
+        let page = Page::new()?;
+        fs::create_dir_all(unsafe { OUTPUT_DOCS_DIR })?;
+
+        let mut tables = TABLES.iter().map(|v| v.key().clone()).collect::<Vec<String>>();
+        tables.sort();
+        let mut enums = ENUMS.iter().map(|v| v.key().clone()).collect::<Vec<String>>();
+        enums.sort();
+        // <target, (table, column)> of the FKs and <enum, (table, column)> of the enum columns
+        let mut fk_refs = BTreeMap::<String, Vec<(String, String)>>::default();
+        let mut enum_refs = BTreeMap::<String, Vec<(String, String)>>::default();
+        for name in tables.iter() {
+            let table = TABLES.get(name).unwrap();
+            for column in table.columns.iter() {
+                if let Some((_, Some(target))) = column.fk.as_ref() {
+                    fk_refs.entry(target.clone()).or_default().push((name.clone(), column.ident.clone()));
+                }
+                for token in column.ty.split(|c: char| !c.is_alphanumeric() && c != '_') {
+                    if ENUMS.contains_key(token) {
+                        enum_refs.entry(String::from(token)).or_default().push((name.clone(), column.ident.clone()));
+                    }
+                }
+            }
+        }
+
+        for name in tables.iter() {
+            let table = TABLES.get(name).unwrap();
+            let mut body = Vec::<String>::default();
+            body.push(page.heading(1, &page.text(name)));
+            let mut facts = vec![format!("Rows: {}", table.rows), format!("Id: {}", page.text(&table.id_type))];
+            if !table.def_keys.is_empty() {
+                facts.push(format!("DefKeys: {}", page.text(&table.def_keys.join(", "))));
+            }
+            body.push(page.para(&facts.join(" · ")));
+
+            body.push(page.heading(2, "Columns"));
+            let rows = table.columns.iter().map(|column| {
+                let fk = match column.fk.as_ref() {
+                    Some((pattern, Some(target))) if tables.contains(target) => format!("{} ({})", page.link(target, target), page.code(pattern)),
+                    Some((pattern, _)) => page.code(pattern),
+                    None => String::default(),
+                };
+                vec![
+                    page.code(&column.ident),
+                    link_enums(&page, &column.ty, &enums),
+                    page.text(&column.comment),
+                    if column.default.is_empty() { String::default() } else { page.code(&column.default) },
+                    fk,
+                ]
+            }).collect::<Vec<Vec<String>>>();
+            body.push(page.table(&["Column", "Type", "Comment", "Default", "FK"], &rows));
+
+            let owned = enums.iter().filter(|v| ENUMS.get(*v).is_some_and(|e| &e.owner == name)).collect::<Vec<&String>>();
+            if !owned.is_empty() {
+                body.push(page.heading(2, "Enums"));
+                body.push(page.list(&owned.iter().map(|v| page.link(v, v)).collect::<Vec<String>>()));
+            }
+            if let Some(refs) = fk_refs.get(name) {
+                body.push(page.heading(2, "Referenced by"));
+                body.push(page.list(&refs.iter().map(|(t, c)| format!("{}.{}", page.link(t, t), page.text(c))).collect::<Vec<String>>()));
+            }
+            page.write(name, name, &body)?;
+        }
+
+        for name in enums.iter() {
+            let def = ENUMS.get(name).unwrap();
+            let mut body = Vec::<String>::default();
+            body.push(page.heading(1, &page.text(name)));
+            if def.owner.is_empty() {
+                body.push(page.para("Shared"));
+            } else if tables.contains(&def.owner) {
+                body.push(page.para(&format!("Defined by {}", page.link(&def.owner, &def.owner))));
+            } else {
+                body.push(page.para(&format!("Defined by {}", page.text(&def.owner))));
+            }
+
+            let rows = def.members.iter().map(|(ident, val, desc)| vec![page.code(ident), val.to_string(), page.text(desc)]).collect::<Vec<Vec<String>>>();
+            body.push(page.table(&["Member", "Value", "Description"], &rows));
+            if let Some(refs) = enum_refs.get(name) {
+                body.push(page.heading(2, "Used by"));
+                body.push(page.list(&refs.iter().map(|(t, c)| format!("{}.{}", page.link(t, t), page.text(c))).collect::<Vec<String>>()));
+            }
+            page.write(name, name, &body)?;
+        }
+
+        let mut body = Vec::<String>::default();
+        body.push(page.heading(1, "Config Tables"));
+        let rows = tables.iter().map(|v| {
+            let table = TABLES.get(v).unwrap();
+            vec![page.link(v, v), table.rows.to_string(), table.columns.len().to_string()]
+        }).collect::<Vec<Vec<String>>>();
+        body.push(page.table(&["Table", "Rows", "Columns"], &rows));
+        body.push(page.heading(2, "Enums"));
+        body.push(page.list(&enums.iter().map(|v| page.link(v, v)).collect::<Vec<String>>()));
+        page.write("index", "Config Tables", &body)
+    }
+}
+
+pub fn check_format() -> Result<()> {
+    Page::new().map(|_| ())
+}
+
+// the enums in the type link to their pages
+fn link_enums(page: &Page, ty: &str, enums: &Vec<String>) -> String {
+    let mut ret = String::default();
+    let mut token = String::default();
+    for c in ty.chars().chain(std::iter::once('\0')) {
+        if c.is_alphanumeric() || c == '_' {
+            token.push(c);
+            continue;
+        }
+        if enums.contains(&token) {
+            ret.push_str(&page.link(&token, &token));
+        } else {
+            ret.push_str(&page.text(&token));
+        }
+        token.clear();
+        if c != '\0' {
+            ret.push_str(&page.text(&c.to_string()));
+        }
+    }
+    ret
+}
+
+// blocks of one page, markdown or html
+struct Page {
+    html: bool,
+}
+
+impl Page {
+    fn new() -> Result<Page> {
+        match unsafe { DOCS_FORMAT } {
+            "md" => Ok(Page { html: false }),
+            "html" => Ok(Page { html: true }),
+            v => Err(Error::new(ErrorKind::InvalidInput, format!("unknown docs format: {}, expected md or html", v))),
+        }
+    }
+
+    fn text(&self, v: &str) -> String {
+        if self.html {
+            v.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+        } else {
+            v.replace('\\', "\\\\").replace('|', "\\|").replace('<', "\\<").replace('*', "\\*").replace('_', "\\_")
+                .replace(['\r', '\n'], " ")
+        }
+    }
+
+    fn code(&self, v: &str) -> String {
+        if self.html {
+            format!("<code>{}</code>", self.text(v))
+        } else {
+            format!("`{}`", v.replace('|', "\\|").replace('`', "'").replace(['\r', '\n'], " "))
+        }
+    }
+
+    fn link(&self, text: &str, page: &str) -> String {
+        if self.html {
+            format!("<a href=\"{}.html\">{}</a>", self.text(page), self.text(text))
+        } else {
+            format!("[{}]({}.md)", self.text(text), page)
+        }
+    }
+
+    fn heading(&self, level: usize, text: &str) -> String {
+        if self.html {
+            format!("<h{}>{}</h{}>", level, text, level)
+        } else {
+            format!("{} {}", "#".repeat(level), text)
+        }
+    }
+
+    fn para(&self, text: &str) -> String {
+        if self.html { format!("<p>{}</p>", text) } else { String::from(text) }
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        if self.html {
+            let items = items.iter().map(|v| format!("<li>{}</li>", v)).collect::<Vec<String>>();
+            format!("<ul>{}{}{}</ul>", LINE_END_FLAG, items.join(LINE_END_FLAG), LINE_END_FLAG)
+        } else {
+            items.iter().map(|v| format!("- {}", v)).collect::<Vec<String>>().join(LINE_END_FLAG)
+        }
+    }
+
+    fn table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        let end = LINE_END_FLAG;
+        if self.html {
+            let mut lines = vec![String::from("<table>")];
+            lines.push(format!("<tr>{}</tr>", headers.iter().map(|v| format!("<th>{}</th>", v)).collect::<String>()));
+            for row in rows.iter() {
+                lines.push(format!("<tr>{}</tr>", row.iter().map(|v| format!("<td>{}</td>", v)).collect::<String>()));
+            }
+            lines.push(String::from("</table>"));
+            lines.join(end)
+        } else {
+            let mut lines = vec![format!("| {} |", headers.join(" | "))];
+            lines.push(format!("|{}", "---|".repeat(headers.len())));
+            for row in rows.iter() {
+                lines.push(format!("| {} |", row.join(" | ")));
+            }
+            lines.join(end)
+        }
+    }
+
+    fn write(&self, file_name: &str, title: &str, blocks: &[String]) -> Result<()> {
+        let end = LINE_END_FLAG;
+        let dest = format!("{}/{}.{}", unsafe { OUTPUT_DOCS_DIR }, file_name, if self.html { "html" } else { "md" });
+        let mut file = File::create(dest)?;
+        if self.html {
+            file.write_fmt(format_args!("<!DOCTYPE html>{}<html>{}<head>{}<meta charset=\"utf-8\">{}<title>{}</title>{}", end, end, end, end, self.text(title), end))?;
+            file.write_fmt(format_args!("<style>table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #ccc; padding: 2px 8px; text-align: left; }}</style>{}", end))?;
+            file.write_fmt(format_args!("</head>{}<body>{}", end, end))?;
+            if file_name != "index" {
+                file.write_fmt(format_args!("<p><a href=\"index.html\">Index</a></p>{}", end))?;
+            }
+            file.write_fmt(format_args!("{}{}", blocks.join(end), end))?;
+            file.write_fmt(format_args!("</body>{}</html>{}", end, end))?;
+        } else {
+            if file_name != "index" {
+                file.write_fmt(format_args!("[Index](index.md){}{}", end, end))?;
+            }
+            file.write_fmt(format_args!("{}{}", blocks.join(&format!("{}{}", end, end)), end))?;
+        }
+        file.flush()
+    }
+}
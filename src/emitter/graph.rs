@@ -0,0 +1,147 @@
+use crate::defs::{OUTPUT_GRAPH_DIR, GRAPH_FOCUS, GRAPH_DEPTH, LINE_END_FLAG};
+use crate::parser::TableModel;
+use super::Emitter;
+use super::json::escape_str;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, Write, Result};
+
+// one FK column: (column, pattern, target tables)
+type FkColumn = (String, String, Vec<String>);
+
+lazy_static! (
+    static ref ROWS: DashMap<String, usize> = DashMap::default();
+    static ref FKS: DashMap<String, Vec<FkColumn>> = DashMap::default();
+);
+
+struct Edge<'a> {
+    from: &'a str,
+    column: &'a str,
+    to: &'a str,
+    pattern: &'a str,
+    kind: &'static str,
+}
+
+// FkGraph.dot and FkGraph.json into the graph dir, table -> table per FK column.
+// the targets are the tables the values were looked up in, so ?/# patterns give the tables their rows name
+pub struct GraphEmitter;
+
+impl Emitter for GraphEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        ROWS.insert(model.name.clone(), model.lines);
+        let fks = model.columns.iter()
+            .filter_map(|v| v.fk.as_ref().map(|fk| (v.ident.to_string(), format!("*{}", fk), v.fk_targets.clone())))
+            .collect::<Vec<FkColumn>>();
+        FKS.insert(model.name.clone(), fks);
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<()> {
+        let mut names = ROWS.iter().map(|v| v.key().clone()).collect::<Vec<String>>();
+        names.sort();
+        let fks = names.iter().map(|v| (v.clone(), FKS.get(v).map(|v| v.clone()).unwrap_or_default())).collect::<Vec<(String, Vec<FkColumn>)>>();
+
+        let mut edges = Vec::<Edge>::default();
+        for (from, columns) in fks.iter() {
+            for (column, pattern, targets) in columns.iter() {
+                let kind = if pattern.contains('?') || pattern.contains('#') {
+                    "dynamic"
+                } else if targets.len() == 1 && pattern[1..].trim_matches(|c| c == '{' || c == '}') == targets[0] {
+                    "simple"
+                } else {
+                    "composite"
+                };
+                for to in targets.iter() {
+                    edges.push(Edge { from, column, to, pattern, kind });
+                }
+            }
+        }
+
+        // targets the build did not parse are nodes without rows
+        let mut nodes = BTreeSet::<&str>::default();
+        nodes.extend(names.iter().map(|v| v.as_str()));
+        nodes.extend(edges.iter().map(|v| v.to));
+
+        let focus = unsafe { GRAPH_FOCUS };
+        if !focus.is_empty() {
+            if !nodes.contains(focus) {
+                return Err(Error::new(ErrorKind::InvalidInput, format!("focus table {} is not in the graph", focus)));
+            }
+            // tables within depth edges of the focus, in either direction
+            let mut near = BTreeSet::<&str>::default();
+            near.insert(focus);
+            let mut frontier = vec![focus];
+            for _ in 0..unsafe { GRAPH_DEPTH } {
+                let mut next = Vec::<&str>::default();
+                for edge in edges.iter() {
+                    for (a, b) in [(edge.from, edge.to), (edge.to, edge.from)] {
+                        if frontier.contains(&a) && near.insert(b) {
+                            next.push(b);
+                        }
+                    }
+                }
+                frontier = next;
+            }
+            nodes.retain(|v| near.contains(v));
+            edges.retain(|v| nodes.contains(v.from) && nodes.contains(v.to));
+        }
+
+        fs::create_dir_all(unsafe { OUTPUT_GRAPH_DIR })?;
+        write_dot(&nodes, &edges, focus)?;
+        write_json(&nodes, &edges, focus)
+    }
+}
+
+fn write_dot(nodes: &BTreeSet<&str>, edges: &Vec<Edge>, focus: &str) -> Result<()> {
+    let end = LINE_END_FLAG;
+    let mut file = File::create(format!("{}/FkGraph.dot", unsafe { OUTPUT_GRAPH_DIR }))?;
+    file.write_fmt(format_args!("digraph FkGraph {{{}", end))?;
+    file.write_fmt(format_args!("\trankdir=LR;{}", end))?;
+    file.write_fmt(format_args!("\tnode [shape=box];{}", end))?;
+    for node in nodes.iter() {
+        let label = match ROWS.get(*node) {
+            Some(rows) => format!("{}\\n{} rows", escape_str(node), *rows),
+            None => escape_str(node),
+        };
+        let style = if *node == focus { ", style=bold" } else if ROWS.contains_key(*node) { "" } else { ", style=dashed" };
+        file.write_fmt(format_args!("\t\"{}\" [label=\"{}\"{}];{}", escape_str(node), label, style, end))?;
+    }
+    for edge in edges.iter() {
+        let style = match edge.kind {
+            "dynamic" => ", style=dashed",
+            "composite" => ", style=dotted",
+            _ => "",
+        };
+        file.write_fmt(format_args!("\t\"{}\" -> \"{}\" [label=\"{}\", tooltip=\"{}\"{}];{}",
+            escape_str(edge.from), escape_str(edge.to), escape_str(edge.column), escape_str(edge.pattern), style, end))?;
+    }
+    file.write_fmt(format_args!("}}{}", end))?;
+    file.flush()
+}
+
+fn write_json(nodes: &BTreeSet<&str>, edges: &Vec<Edge>, focus: &str) -> Result<()> {
+    let end = LINE_END_FLAG;
+    let mut file = File::create(format!("{}/FkGraph.json", unsafe { OUTPUT_GRAPH_DIR }))?;
+    file.write_fmt(format_args!("{{{}", end))?;
+    if !focus.is_empty() {
+        file.write_fmt(format_args!("\t\"focus\": \"{}\",{}", escape_str(focus), end))?;
+        file.write_fmt(format_args!("\t\"depth\": {},{}", unsafe { GRAPH_DEPTH }, end))?;
+    }
+    file.write_fmt(format_args!("\t\"nodes\": [{}", end))?;
+    for (i, node) in nodes.iter().enumerate() {
+        let rows = ROWS.get(*node).map(|v| v.to_string()).unwrap_or(String::from("null"));
+        file.write_fmt(format_args!("\t\t{{\"name\": \"{}\", \"rows\": {}}}{}{}", escape_str(node), rows, if i + 1 < nodes.len() { "," } else { "" }, end))?;
+    }
+    file.write_fmt(format_args!("\t],{}", end))?;
+    file.write_fmt(format_args!("\t\"edges\": [{}", end))?;
+    for (i, edge) in edges.iter().enumerate() {
+        file.write_fmt(format_args!("\t\t{{\"from\": \"{}\", \"column\": \"{}\", \"to\": \"{}\", \"pattern\": \"{}\", \"kind\": \"{}\"}}{}{}",
+            escape_str(edge.from), escape_str(edge.column), escape_str(edge.to), escape_str(edge.pattern), edge.kind,
+            if i + 1 < edges.len() { "," } else { "" }, end))?;
+    }
+    file.write_fmt(format_args!("\t]{}", end))?;
+    file.write_fmt(format_args!("}}{}", end))?;
+    file.flush()
+}
@@ -0,0 +1,101 @@
+use crate::defs::{OUTPUT_DATA_DIR, LINE_END_FLAG};
+use crate::parser::cell_value::*;
+use crate::parser::TableModel;
+use super::Emitter;
+use std::fs::{self, File};
+use std::io::{Write, Result};
+
+// {Name}.json into the data dir, one object per row keyed by identifier
+pub struct JsonEmitter;
+
+impl Emitter for JsonEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_DATA_DIR })?;
+        let output_path = format!("{}/{}.json", unsafe { OUTPUT_DATA_DIR }, model.name);
+        let mut file = File::create(output_path)?;
+        let end = LINE_END_FLAG;
+
+        file.write("[".as_bytes())?;
+        for row in 0..model.lines {
+            file.write_fmt(format_args!("{}\t{{", end))?;
+            for (i, column) in model.columns.iter().enumerate() {
+                if i > 0 {
+                    file.write(", ".as_bytes())?;
+                }
+                file.write_fmt(format_args!("\"{}\": ", escape_str(&column.ident)))?;
+                write_value(column.value(row), &mut file)?;
+            }
+            file.write("}".as_bytes())?;
+            if row + 1 < model.lines {
+                file.write(",".as_bytes())?;
+            }
+        }
+        file.write_fmt(format_args!("{}]{}", end, end))?;
+        file.flush()
+    }
+}
+
+// LStrings are written as ids, enums as identifiers, tuples as arrays
+pub fn write_value<W: Write + ?Sized>(v: &CellValue, stream: &mut W) -> Result<()> {
+    match v {
+        CellValue::DEnum(EnumValue(_, val, _)) => {
+            if val.is_empty() {
+                stream.write("null".as_bytes())?;
+            } else {
+                stream.write_fmt(format_args!("\"{}\"", escape_str(val)))?;
+            }
+        }
+        CellValue::DBool(BoolValue(b)) => { stream.write(b.to_string().as_bytes())?; }
+        CellValue::DLString(LStringValue(_, id)) => { stream.write(id.to_string().as_bytes())?; }
+        CellValue::DString(StringValue(s)) => {
+            let s = s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s);
+            stream.write_fmt(format_args!("\"{}\"", escape_str(s)))?;
+        }
+        CellValue::DShort(ShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DUShort(UShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DSByte(SByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DByte(ByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DInt(IntValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DUInt(UIntValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DFloat(FloatValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DDouble(DoubleValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        // raw text of the cell, the reader of the type knows its layout
+        CellValue::DCustom(CustomValue(_, params)) => { stream.write_fmt(format_args!("\"{}\"", escape_str(params)))?; }
+        CellValue::DShortList(ShortListValue(ArrayValue(arr))) => { write_array(arr.iter().skip(1), stream)?; }
+        CellValue::DTuple(TupleValue(arr)) => { write_array(arr.iter(), stream)?; }
+        CellValue::DValueTuple(ValueTupleValue(arr)) => { write_array(arr.iter(), stream)?; }
+        CellValue::DArray(ArrayValue(arr)) => { write_array(arr.iter().skip(1), stream)?; }
+        CellValue::DList(ListValue(arr)) => { write_array(arr.iter().skip(1), stream)?; }
+        CellValue::DNone(NoneValue(ty)) => { stream.write(super::empty_literal(ty).unwrap_or("null").as_bytes())?; }
+        CellValue::DError(_) => { stream.write("null".as_bytes())?; }
+    }
+    Ok(())
+}
+
+fn write_array<'a, W: Write + ?Sized>(arr: impl Iterator<Item = &'a CellValue>, stream: &mut W) -> Result<()> {
+    stream.write("[".as_bytes())?;
+    for (i, v) in arr.enumerate() {
+        if i > 0 {
+            stream.write(",".as_bytes())?;
+        }
+        write_value(v, stream)?;
+    }
+    stream.write("]".as_bytes())?;
+    Ok(())
+}
+
+pub fn escape_str(v: &str) -> String {
+    let mut ret = String::with_capacity(v.len());
+    for c in v.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret
+}
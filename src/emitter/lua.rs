@@ -0,0 +1,194 @@
+use crate::defs::{OUTPUT_LUA_DIR, LINE_END_FLAG};
+use crate::naming;
+use crate::parser::cell_value::*;
+use crate::parser::{TableModel, EnumDef};
+use super::Emitter;
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{Write, Result};
+
+const KEYWORDS: [&str; 22] = [
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if",
+    "in", "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+// {Name}.lua into the lua dir: an array of rows keyed by identifier, with the DefKeys as {Name}.DefKey.
+// E{Base}{Name}.lua are tables of constants, required by the tables which use them
+pub struct LuaEmitter;
+
+impl Emitter for LuaEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_LUA_DIR })?;
+        for def in model.enums.iter() {
+            self.emit_enum(def)?;
+        }
+
+        let name = &model.name;
+        let end = LINE_END_FLAG;
+        let mut rows = Vec::<u8>::default();
+        let mut enums = BTreeSet::<String>::default();
+        for row in 0..model.lines {
+            rows.write("\t{ ".as_bytes())?;
+            for (i, column) in model.columns.iter().enumerate() {
+                if i > 0 {
+                    rows.write(", ".as_bytes())?;
+                }
+                rows.write_fmt(format_args!("{} = ", key(&column.ident)))?;
+                write_value(column.value(row), &mut enums, &mut rows)?;
+            }
+            rows.write_fmt(format_args!(" }},{}", end))?;
+        }
+
+        let output_path = format!("{}/{}.lua", unsafe { OUTPUT_LUA_DIR }, name);
+        let mut file = File::create(output_path)?;
+        file.write_fmt(format_args!("-- This File is generated by the program, DO NOT EDIT MANUALLY!{}{}", end, end))?;
+        for v in enums.iter() {
+            file.write_fmt(format_args!("local {} = require(\"{}\"){}", v, v, end))?;
+        }
+        if !enums.is_empty() {
+            file.write(end.as_bytes())?;
+        }
+
+        file.write_fmt(format_args!("local {} = {{{}", name, end))?;
+        file.write(&rows)?;
+        file.write_fmt(format_args!("}}{}{}", end, end))?;
+
+        let def_keys = model.def_keys();
+        if !def_keys.is_empty() {
+            file.write_fmt(format_args!("{}.DefKey = {{{}", name, end))?;
+            for (key_name, id) in def_keys.iter() {
+                file.write_fmt(format_args!("\t{} = {},{}", key(key_name), id, end))?;
+            }
+            file.write_fmt(format_args!("}}{}{}", end, end))?;
+        }
+        file.write_fmt(format_args!("return {}{}", name, end))?;
+        file.flush()
+    }
+
+    fn emit_enum(&self, def: &EnumDef) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_LUA_DIR })?;
+        let type_name = naming::enum_type(&def.owner, &def.name);
+        let output_path = format!("{}/{}.lua", unsafe { OUTPUT_LUA_DIR }, type_name);
+        let mut file = File::create(output_path)?;
+        let end = LINE_END_FLAG;
+
+        file.write_fmt(format_args!("-- This File is generated by the program, DO NOT EDIT MANUALLY!{}{}", end, end))?;
+        file.write_fmt(format_args!("return {{{}", end))?;
+        for (ident, val, desc) in def.members.iter() {
+            file.write_fmt(format_args!("\t{} = {}, -- {}{}", key(ident), val, desc.replace(['\r', '\n'], " "), end))?;
+        }
+        file.write_fmt(format_args!("}}{}", end))?;
+        file.flush()
+    }
+}
+
+// keywords are not valid as names
+fn key(v: &str) -> String {
+    if KEYWORDS.contains(&v) {
+        format!("[\"{}\"]", v)
+    } else {
+        String::from(v)
+    }
+}
+
+// member of a table, indexed by string like key() names it
+fn field(table: &str, v: &str) -> String {
+    if KEYWORDS.contains(&v) {
+        format!("{}[\"{}\"]", table, v)
+    } else {
+        format!("{}.{}", table, v)
+    }
+}
+
+// LStrings are written as ids, tuples as arrays, the required enum modules are collected into enums
+fn write_value<W: Write + ?Sized>(v: &CellValue, enums: &mut BTreeSet<String>, stream: &mut W) -> Result<()> {
+    match v {
+        CellValue::DEnum(EnumValue(name, val, owner)) => {
+            if val.is_empty() {
+                stream.write("nil".as_bytes())?;
+            } else {
+                let type_name = naming::enum_type(owner, name);
+                stream.write(field(&type_name, val).as_bytes())?;
+                enums.insert(type_name);
+            }
+        }
+        CellValue::DBool(BoolValue(b)) => { stream.write(b.to_string().as_bytes())?; }
+        CellValue::DLString(LStringValue(_, id)) => { stream.write(id.to_string().as_bytes())?; }
+        CellValue::DString(StringValue(s)) => {
+            let s = s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s);
+            stream.write_fmt(format_args!("\"{}\"", escape_str(s)))?;
+        }
+        CellValue::DShort(ShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DUShort(UShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DSByte(SByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DByte(ByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DInt(IntValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DUInt(UIntValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DFloat(FloatValue(n)) => { stream.write(float_str(*n as f64, n.to_string()).as_bytes())?; }
+        CellValue::DDouble(DoubleValue(n)) => { stream.write(float_str(*n, n.to_string()).as_bytes())?; }
+        CellValue::DCustom(CustomValue(_, params)) => { stream.write_fmt(format_args!("\"{}\"", escape_str(params)))?; }
+        CellValue::DShortList(ShortListValue(ArrayValue(arr))) => { write_array(arr.iter().skip(1), enums, stream)?; }
+        CellValue::DTuple(TupleValue(arr)) => { write_array(arr.iter(), enums, stream)?; }
+        CellValue::DValueTuple(ValueTupleValue(arr)) => { write_array(arr.iter(), enums, stream)?; }
+        CellValue::DArray(ArrayValue(arr)) => { write_array(arr.iter().skip(1), enums, stream)?; }
+        CellValue::DList(ListValue(arr)) => { write_array(arr.iter().skip(1), enums, stream)?; }
+        CellValue::DNone(NoneValue(ty)) => { stream.write(super::empty_literal(ty).unwrap_or("nil").as_bytes())?; }
+        CellValue::DError(_) => { stream.write("nil".as_bytes())?; }
+    }
+    Ok(())
+}
+
+fn write_array<'a, W: Write + ?Sized>(arr: impl Iterator<Item = &'a CellValue>, enums: &mut BTreeSet<String>, stream: &mut W) -> Result<()> {
+    stream.write("{".as_bytes())?;
+    for (i, v) in arr.enumerate() {
+        if i > 0 {
+            stream.write(", ".as_bytes())?;
+        }
+        write_value(v, enums, stream)?;
+    }
+    stream.write("}".as_bytes())?;
+    Ok(())
+}
+
+// floats keep their subtype in lua 5.3+, s is the shortest text of the value
+fn float_str(v: f64, s: String) -> String {
+    if v.is_nan() {
+        String::from("(0/0)")
+    } else if v.is_infinite() {
+        String::from(if v > 0.0 { "math.huge" } else { "-math.huge" })
+    } else {
+        if s.contains(|c| c == '.' || c == 'e') { s } else { format!("{}.0", s) }
+    }
+}
+
+fn escape_str(v: &str) -> String {
+    let mut ret = String::with_capacity(v.len());
+    for c in v.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 || c as u32 == 0x7f => ret.push_str(&format!("\\{:03}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn keyword_members_are_indexed_by_string() {
+        let mut enums = BTreeSet::<String>::default();
+        let mut buf = Vec::<u8>::default();
+        let v = CellValue::DEnum(EnumValue(Rc::new(String::from("Step")), Rc::new(String::from("end")), Rc::new(String::from("Quest"))));
+        write_value(&v, &mut enums, &mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "EQuestStep[\"end\"]");
+        assert_eq!(field("EQuestStep", "Start"), "EQuestStep.Start");
+    }
+}
@@ -0,0 +1,204 @@
+use crate::defs::{OUTPUT_DATA_DIR, OUTPUT_SCRIPT_CODE_DIR, DEFAULT_DEST_SUFFIX, LINE_END_FLAG, GEN_MSGPACK_CLASSES};
+use crate::naming;
+use crate::parser::cell_value::*;
+use crate::parser::TableModel;
+use super::Emitter;
+use super::csharp::param_type;
+use std::fs::{self, File};
+use std::io::{Write, Result};
+
+// {Name}.msgpack into the data dir: an array of rows, every row an array of the columns in order.
+// enums are written as identifiers, LStrings as ids, tuples and ShortLists as arrays
+pub struct MsgPackEmitter;
+
+impl Emitter for MsgPackEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_DATA_DIR })?;
+        let output_path = format!("{}/{}.msgpack", unsafe { OUTPUT_DATA_DIR }, model.name);
+
+        let mut buf = Vec::<u8>::default();
+        write_array_len(model.lines, &mut buf);
+        for row in 0..model.lines {
+            write_array_len(model.columns.len(), &mut buf);
+            for column in model.columns.iter() {
+                write_value(column.value(row), &mut buf);
+            }
+        }
+
+        let mut file = File::create(output_path)?;
+        file.write_all(&buf)?;
+        file.flush()?;
+
+        if unsafe { GEN_MSGPACK_CLASSES } {
+            gen_pack_class(model)?;
+        }
+        Ok(())
+    }
+}
+
+// {Name}PackItem: [MessagePackObject] class of one row, keys are the column indexes
+fn gen_pack_class(model: &TableModel) -> Result<()> {
+    let name = &model.name;
+    let class_name = naming::of(name).item_class(&format!("{}Pack", name));
+    let dest = format!("{}/{}.{}", unsafe { OUTPUT_SCRIPT_CODE_DIR }, class_name, DEFAULT_DEST_SUFFIX);
+    let mut file = File::create(dest)?;
+    let end = LINE_END_FLAG;
+
+    file.write_fmt(format_args!("using System;{}", end))?;
+    file.write_fmt(format_args!("using System.Collections.Generic;{}", end))?;
+    file.write_fmt(format_args!("using MessagePack;{}", end))?;
+    for v in naming::enum_usings(model).iter() {
+        file.write_fmt(format_args!("using {};{}", v, end))?;
+    }
+    file.write(end.as_bytes())?;
+    file.write_fmt(format_args!("#pragma warning disable 1591{}{}", end, end))?;
+    file.write_fmt(format_args!("namespace {}{}{{{}", naming::of(name).namespace, end, end))?;
+    file.write_fmt(format_args!("\t/// <summary>{}", end))?;
+    file.write_fmt(format_args!("\t/// one row of {}.msgpack, which is a List of these. enums are identifiers, read them with DynamicEnumAsStringResolver{}", name, end))?;
+    file.write_fmt(format_args!("\t/// </summary>{}", end))?;
+    file.write_fmt(format_args!("\t[MessagePackObject]{}", end))?;
+    file.write_fmt(format_args!("\tpublic class {}{}\t{{{}", class_name, end, end))?;
+
+    for (i, column) in model.columns.iter().enumerate() {
+        if i > 0 {
+            file.write(end.as_bytes())?;
+        }
+        if let Some(comment) = column.comment.as_ref() {
+            file.write_fmt(format_args!("\t\t/// <summary>{}", end))?;
+            file.write_fmt(format_args!("\t\t/// {}{}", comment.replace(['\r', '\n'], " "), end))?;
+            file.write_fmt(format_args!("\t\t/// </summary>{}", end))?;
+        }
+        // ShortList has no formatter, its values are a plain array
+        let ty = param_type(column, name).replace("ShortList", "short[]");
+        file.write_fmt(format_args!("\t\t[Key({})]{}", i, end))?;
+        file.write_fmt(format_args!("\t\tpublic {} {};{}", ty, column.ident, end))?;
+    }
+
+    file.write_fmt(format_args!("\t}}{}", end))?;
+    file.write("}".as_bytes())?;
+    file.flush()
+}
+
+pub fn write_value(v: &CellValue, buf: &mut Vec<u8>) {
+    match v {
+        CellValue::DEnum(EnumValue(_, val, _)) => {
+            if val.is_empty() {
+                buf.push(0xc0);
+            } else {
+                write_str(val, buf);
+            }
+        }
+        CellValue::DBool(BoolValue(b)) => buf.push(if *b { 0xc3 } else { 0xc2 }),
+        CellValue::DLString(LStringValue(_, id)) => write_int(*id as i64, buf),
+        CellValue::DString(StringValue(s)) => {
+            let s = s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s);
+            write_str(s, buf);
+        }
+        CellValue::DShort(ShortValue(n)) => write_int(*n as i64, buf),
+        CellValue::DUShort(UShortValue(n)) => write_int(*n as i64, buf),
+        CellValue::DSByte(SByteValue(n)) => write_int(*n as i64, buf),
+        CellValue::DByte(ByteValue(n)) => write_int(*n as i64, buf),
+        CellValue::DInt(IntValue(n)) => write_int(*n as i64, buf),
+        CellValue::DUInt(UIntValue(n)) => write_int(*n as i64, buf),
+        CellValue::DFloat(FloatValue(n)) => {
+            buf.push(0xca);
+            buf.extend_from_slice(&n.to_be_bytes());
+        }
+        CellValue::DDouble(DoubleValue(n)) => {
+            buf.push(0xcb);
+            buf.extend_from_slice(&n.to_be_bytes());
+        }
+        CellValue::DCustom(CustomValue(_, params)) => write_str(params, buf),
+        // the dummy prototype is not written
+        CellValue::DShortList(ShortListValue(ArrayValue(arr))) | CellValue::DArray(ArrayValue(arr)) | CellValue::DList(ListValue(arr)) => {
+            if arr.is_empty() {
+                buf.push(0xc0);
+            } else {
+                write_array_len(arr.len() - 1, buf);
+                for v in arr.iter().skip(1) {
+                    write_value(v, buf);
+                }
+            }
+        }
+        CellValue::DTuple(TupleValue(arr)) | CellValue::DValueTuple(ValueTupleValue(arr)) => {
+            write_array_len(arr.len(), buf);
+            for v in arr.iter() {
+                write_value(v, buf);
+            }
+        }
+        CellValue::DNone(NoneValue(ty)) => {
+            match (super::empty_literal(ty), CellValue::get_type(ty)) {
+                (None, _) => buf.push(0xc0),
+                (Some(_), CellValue::DBool(_)) => buf.push(0xc2),
+                (Some(_), CellValue::DFloat(_)) => write_value(&CellValue::DFloat(FloatValue(0_f32)), buf),
+                (Some(_), CellValue::DDouble(_)) => write_value(&CellValue::DDouble(DoubleValue(0_f64)), buf),
+                (Some(v), _) => write_int(v.parse::<i64>().unwrap_or_default(), buf),
+            }
+        }
+        CellValue::DError(_) => buf.push(0xc0),
+    }
+}
+
+// smallest encoding of the value, readers widen it to the field type
+fn write_int(n: i64, buf: &mut Vec<u8>) {
+    if n >= 0 {
+        if n < 0x80 {
+            buf.push(n as u8);
+        } else if n <= u8::MAX as i64 {
+            buf.push(0xcc);
+            buf.push(n as u8);
+        } else if n <= u16::MAX as i64 {
+            buf.push(0xcd);
+            buf.extend_from_slice(&(n as u16).to_be_bytes());
+        } else if n <= u32::MAX as i64 {
+            buf.push(0xce);
+            buf.extend_from_slice(&(n as u32).to_be_bytes());
+        } else {
+            buf.push(0xcf);
+            buf.extend_from_slice(&(n as u64).to_be_bytes());
+        }
+    } else if n >= -32 {
+        buf.push(n as i8 as u8);
+    } else if n >= i8::MIN as i64 {
+        buf.push(0xd0);
+        buf.push(n as i8 as u8);
+    } else if n >= i16::MIN as i64 {
+        buf.push(0xd1);
+        buf.extend_from_slice(&(n as i16).to_be_bytes());
+    } else if n >= i32::MIN as i64 {
+        buf.push(0xd2);
+        buf.extend_from_slice(&(n as i32).to_be_bytes());
+    } else {
+        buf.push(0xd3);
+        buf.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+fn write_str(v: &str, buf: &mut Vec<u8>) {
+    let len = v.len();
+    if len < 32 {
+        buf.push(0xa0 | len as u8);
+    } else if len <= u8::MAX as usize {
+        buf.push(0xd9);
+        buf.push(len as u8);
+    } else if len <= u16::MAX as usize {
+        buf.push(0xda);
+        buf.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        buf.push(0xdb);
+        buf.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+    buf.extend_from_slice(v.as_bytes());
+}
+
+fn write_array_len(len: usize, buf: &mut Vec<u8>) {
+    if len < 16 {
+        buf.push(0x90 | len as u8);
+    } else if len <= u16::MAX as usize {
+        buf.push(0xdc);
+        buf.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        buf.push(0xdd);
+        buf.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+}
@@ -0,0 +1,289 @@
+use crate::defs::{OUTPUT_DATA_DIR, OUTPUT_PROTO_DIR, LINE_END_FLAG};
+use crate::naming;
+use crate::parser::cell_value::*;
+use crate::parser::{TableModel, EnumDef};
+use super::Emitter;
+use super::binary::{Shape, shape_of};
+use super::csharp::{param_type, generic_args};
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{Write, Result};
+
+// {Name}.proto and E{Base}{Name}.proto into the proto dir, {Name}.pb into the data dir.
+// {Name}.pb is a serialized {Name}Table, field numbers of {Name}Row are the column indexes + 1
+pub struct ProtoEmitter;
+
+impl Emitter for ProtoEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_PROTO_DIR })?;
+        for def in model.enums.iter() {
+            self.emit_enum(def)?;
+        }
+        gen_schema(model)?;
+
+        fs::create_dir_all(unsafe { OUTPUT_DATA_DIR })?;
+        let shapes = model.columns.iter().map(|v| shape_of(&v.ty)).collect::<Vec<Shape>>();
+        let mut buf = Vec::<u8>::default();
+        for row in 0..model.lines {
+            let mut msg = Vec::<u8>::default();
+            for (i, (column, shape)) in model.columns.iter().zip(shapes.iter()).enumerate() {
+                write_field(i as u32 + 1, shape, column.value(row), model, &mut msg);
+            }
+            write_record(1, Wire::Len(msg), &mut buf);
+        }
+
+        let output_path = format!("{}/{}.pb", unsafe { OUTPUT_DATA_DIR }, model.name);
+        let mut file = File::create(output_path)?;
+        file.write_all(&buf)?;
+        file.flush()
+    }
+
+    fn emit_enum(&self, def: &EnumDef) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_PROTO_DIR })?;
+        let type_name = naming::enum_type(&def.owner, &def.name);
+        let dest = format!("{}/{}.proto", unsafe { OUTPUT_PROTO_DIR }, type_name);
+        let mut file = File::create(dest)?;
+        let end = LINE_END_FLAG;
+
+        write_header(&mut file)?;
+        file.write_fmt(format_args!("enum {} {{{}", type_name, end))?;
+        // proto3 enums start with 0, members share the package scope so they are prefixed
+        if !def.members.iter().any(|v| v.1 == 0) {
+            file.write_fmt(format_args!("  {}_Unspecified = 0;{}", type_name, end))?;
+        }
+        let (zero, rest): (Vec<_>, Vec<_>) = def.members.iter().partition(|v| v.1 == 0);
+        for (ident, val, desc) in zero.into_iter().chain(rest.into_iter()) {
+            file.write_fmt(format_args!("  {}_{} = {}; // {}{}", type_name, ident, val, desc.replace(['\r', '\n'], " "), end))?;
+        }
+        if !def.reserved.is_empty() {
+            let vals = def.reserved.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+            file.write_fmt(format_args!("  reserved {};{}", vals.join(", "), end))?;
+        }
+        file.write("}".as_bytes())?;
+        file.write(end.as_bytes())?;
+        file.flush()
+    }
+}
+
+fn write_header<W: Write + ?Sized>(stream: &mut W) -> Result<()> {
+    let end = LINE_END_FLAG;
+    stream.write_fmt(format_args!("// This File is generated by the program, DO NOT EDIT MANUALLY!{}", end))?;
+    stream.write_fmt(format_args!("syntax = \"proto3\";{}{}", end, end))?;
+    stream.write_fmt(format_args!("package config;{}", end))?;
+    // the C# enums of the config classes live in the global namespace
+    stream.write_fmt(format_args!("option csharp_namespace = \"{}.Proto\";{}{}", naming::project().namespace, end, end))?;
+    Ok(())
+}
+
+fn gen_schema(model: &TableModel) -> Result<()> {
+    let name = &model.name;
+    let end = LINE_END_FLAG;
+    let mut imports = BTreeSet::<String>::default();
+    let mut nested = Vec::<String>::default();
+    let mut fields = Vec::<String>::default();
+
+    for (i, column) in model.columns.iter().enumerate() {
+        let (repeated, ty) = field_type(&shape_of(&column.ty), &param_type(column, name), &column.ident, &naming::of(name).item_suffix, &mut nested, &mut imports);
+        let comment = column.comment.as_ref().map(|v| format!(" // {}", v.replace(['\r', '\n'], " "))).unwrap_or_default();
+        fields.push(format!("  {}{} {} = {};{}", if repeated { "repeated " } else { "" }, ty, column.ident, i + 1, comment));
+    }
+
+    let dest = format!("{}/{}.proto", unsafe { OUTPUT_PROTO_DIR }, name);
+    let mut file = File::create(dest)?;
+    write_header(&mut file)?;
+    for v in imports.iter() {
+        file.write_fmt(format_args!("import \"{}.proto\";{}", v, end))?;
+    }
+    if !imports.is_empty() {
+        file.write(end.as_bytes())?;
+    }
+
+    file.write_fmt(format_args!("message {}Row {{{}", name, end))?;
+    for v in nested.iter() {
+        file.write_fmt(format_args!("  {}{}", v, end))?;
+    }
+    for v in fields.iter() {
+        file.write_fmt(format_args!("{}{}", v, end))?;
+    }
+    file.write_fmt(format_args!("}}{}{}", end, end))?;
+
+    file.write_fmt(format_args!("// content of {}.pb{}", name, end))?;
+    file.write_fmt(format_args!("message {}Table {{{}", name, end))?;
+    file.write_fmt(format_args!("  repeated {}Row rows = 1;{}", name, end))?;
+    file.write_fmt(format_args!("}}{}", end))?;
+    file.flush()
+}
+
+// (repeated, type) of one field; nested lists and tuples become messages inside the row,
+// suffix names the elements, Item by default
+fn field_type(shape: &Shape, ty: &str, hint: &str, suffix: &str, nested: &mut Vec<String>, imports: &mut BTreeSet<String>) -> (bool, String) {
+    match shape {
+        Shape::Bool => (false, String::from("bool")),
+        Shape::SByte | Shape::Short | Shape::Int => (false, String::from("sint32")),
+        Shape::Byte | Shape::UShort | Shape::UInt => (false, String::from("uint32")),
+        Shape::Float => (false, String::from("float")),
+        Shape::Double => (false, String::from("double")),
+        Shape::String | Shape::Custom => (false, String::from("string")),
+        Shape::ShortList => (true, String::from("sint32")),
+        Shape::Enum => {
+            imports.insert(String::from(ty));
+            (false, String::from(ty))
+        }
+        Shape::Array(inner) | Shape::List(inner) => {
+            let inner_ty = match shape {
+                Shape::Array(_) => String::from(ty.strip_suffix("[]").unwrap_or(ty)),
+                _ => generic_args(ty).pop().unwrap_or_default(),
+            };
+            let (repeated, inner_ty) = field_type(inner, &inner_ty, &format!("{}{}", hint, suffix), suffix, nested, imports);
+            if repeated {
+                let msg = format!("{}List", hint);
+                nested.push(format!("message {} {{ repeated {} Values = 1; }}", msg, inner_ty));
+                (true, msg)
+            } else {
+                (true, inner_ty)
+            }
+        }
+        Shape::Tuple(shapes) | Shape::ValueTuple(shapes) => {
+            let msg = format!("{}Tuple", hint);
+            let mut items = Vec::default();
+            for (i, (s, t)) in shapes.iter().zip(generic_args(ty).iter()).enumerate() {
+                let (repeated, t) = field_type(s, t, &format!("{}{}{}", hint, suffix, i + 1), suffix, nested, imports);
+                items.push(format!("{}{} Item{} = {};", if repeated { "repeated " } else { "" }, t, i + 1, i + 1));
+            }
+            nested.push(format!("message {} {{ {} }}", msg, items.join(" ")));
+            (false, msg)
+        }
+    }
+}
+
+enum Wire {
+    Varint(u64),
+    Fixed32(u32),
+    Fixed64(u64),
+    Len(Vec<u8>),
+}
+
+fn is_packed(shape: &Shape) -> bool {
+    !matches!(shape, Shape::String | Shape::Custom | Shape::ShortList | Shape::Array(_) | Shape::List(_) | Shape::Tuple(_) | Shape::ValueTuple(_))
+}
+
+fn write_field(no: u32, shape: &Shape, v: &CellValue, model: &TableModel, buf: &mut Vec<u8>) {
+    match (shape, v) {
+        (Shape::Array(inner), CellValue::DArray(ArrayValue(arr))) | (Shape::List(inner), CellValue::DList(ListValue(arr))) => {
+            // the dummy prototype is not written
+            if is_packed(inner) {
+                let mut packed = Vec::<u8>::default();
+                for v in arr.iter().skip(1) {
+                    match wire(inner, v, model) {
+                        Some(Wire::Varint(n)) => write_varint(n, &mut packed),
+                        Some(Wire::Fixed32(n)) => packed.extend_from_slice(&n.to_le_bytes()),
+                        Some(Wire::Fixed64(n)) => packed.extend_from_slice(&n.to_le_bytes()),
+                        _ => write_varint(0, &mut packed),
+                    }
+                }
+                if !packed.is_empty() {
+                    write_record(no, Wire::Len(packed), buf);
+                }
+            } else {
+                for v in arr.iter().skip(1) {
+                    if let Shape::Array(_) | Shape::List(_) | Shape::ShortList = **inner {
+                        // {Ident}List wrapper
+                        let mut msg = Vec::<u8>::default();
+                        write_field(1, inner, v, model, &mut msg);
+                        write_record(no, Wire::Len(msg), buf);
+                    } else {
+                        write_record(no, wire(inner, v, model).unwrap_or(Wire::Len(Vec::default())), buf);
+                    }
+                }
+            }
+        }
+        _ => {
+            if let Some(w) = wire(shape, v, model) {
+                write_record(no, w, buf);
+            }
+        }
+    }
+}
+
+// None for nothing written: empty cells of strings, lists and tuples
+fn wire(shape: &Shape, v: &CellValue, model: &TableModel) -> Option<Wire> {
+    match (shape, v) {
+        (Shape::Bool, CellValue::DBool(BoolValue(b))) => Some(Wire::Varint(*b as u64)),
+        (Shape::SByte, CellValue::DSByte(SByteValue(n))) => Some(Wire::Varint(zigzag(*n as i32))),
+        (Shape::Short, CellValue::DShort(ShortValue(n))) => Some(Wire::Varint(zigzag(*n as i32))),
+        (Shape::Int, CellValue::DInt(IntValue(n))) => Some(Wire::Varint(zigzag(*n))),
+        (Shape::Int, CellValue::DLString(LStringValue(_, id))) => Some(Wire::Varint(zigzag(*id))),
+        (Shape::Byte, CellValue::DByte(ByteValue(n))) => Some(Wire::Varint(*n as u64)),
+        (Shape::UShort, CellValue::DUShort(UShortValue(n))) => Some(Wire::Varint(*n as u64)),
+        (Shape::UInt, CellValue::DUInt(UIntValue(n))) => Some(Wire::Varint(*n as u64)),
+        (Shape::Float, CellValue::DFloat(FloatValue(n))) => Some(Wire::Fixed32(n.to_bits())),
+        (Shape::Double, CellValue::DDouble(DoubleValue(n))) => Some(Wire::Fixed64(n.to_bits())),
+        (Shape::String, CellValue::DString(StringValue(s))) => {
+            let s = s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s);
+            Some(Wire::Len(s.as_bytes().to_vec()))
+        }
+        (Shape::Custom, CellValue::DCustom(CustomValue(_, params))) => Some(Wire::Len(params.as_bytes().to_vec())),
+        // negative values are sign extended to 10 bytes, as protobuf does
+        (Shape::Enum, CellValue::DEnum(EnumValue(name, ident, owner))) => {
+            let val = model.enum_value(owner, name, ident).unwrap_or_default();
+            Some(Wire::Varint(val as i64 as u64))
+        }
+        (Shape::ShortList, CellValue::DShortList(ShortListValue(ArrayValue(arr)))) => {
+            let mut packed = Vec::<u8>::default();
+            for v in arr.iter().skip(1) {
+                if let CellValue::DShort(ShortValue(n)) = v {
+                    write_varint(zigzag(*n as i32), &mut packed);
+                }
+            }
+            if packed.is_empty() { None } else { Some(Wire::Len(packed)) }
+        }
+        (Shape::Tuple(shapes), CellValue::DTuple(TupleValue(arr))) | (Shape::ValueTuple(shapes), CellValue::DValueTuple(ValueTupleValue(arr))) => {
+            let mut msg = Vec::<u8>::default();
+            for (i, (s, v)) in shapes.iter().zip(arr.iter()).enumerate() {
+                write_field(i as u32 + 1, s, v, model, &mut msg);
+            }
+            Some(Wire::Len(msg))
+        }
+        // empty cells, -1 for the signed ones
+        (Shape::Bool | Shape::Byte | Shape::UShort | Shape::UInt, _) => Some(Wire::Varint(0)),
+        (Shape::SByte | Shape::Short | Shape::Int, _) => Some(Wire::Varint(zigzag(-1))),
+        (Shape::Float, _) => Some(Wire::Fixed32(0)),
+        (Shape::Double, _) => Some(Wire::Fixed64(0)),
+        (Shape::Enum, _) => Some(Wire::Varint(0)),
+        _ => None,
+    }
+}
+
+fn write_record(no: u32, w: Wire, buf: &mut Vec<u8>) {
+    match w {
+        Wire::Varint(n) => {
+            write_varint((no as u64) << 3, buf);
+            write_varint(n, buf);
+        }
+        Wire::Fixed64(n) => {
+            write_varint((no as u64) << 3 | 1, buf);
+            buf.extend_from_slice(&n.to_le_bytes());
+        }
+        Wire::Len(v) => {
+            write_varint((no as u64) << 3 | 2, buf);
+            write_varint(v.len() as u64, buf);
+            buf.extend_from_slice(&v);
+        }
+        Wire::Fixed32(n) => {
+            write_varint((no as u64) << 3 | 5, buf);
+            buf.extend_from_slice(&n.to_le_bytes());
+        }
+    }
+}
+
+fn write_varint(mut v: u64, buf: &mut Vec<u8>) {
+    while v >= 0x80 {
+        buf.push((v as u8 & 0x7f) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+fn zigzag(v: i32) -> u64 {
+    ((v << 1) ^ (v >> 31)) as u32 as u64
+}
@@ -0,0 +1,350 @@
+use crate::defs::{OUTPUT_RS_DIR, LINE_END_FLAG};
+use crate::naming;
+use crate::parser::cell_value::*;
+use crate::parser::{TableModel, EnumDef};
+use super::Emitter;
+use super::binary::{Shape, shape_of};
+use super::csharp::{param_type, generic_args};
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{Write, Result};
+
+const KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "yield",
+];
+
+// {name}.rs into the rs dir: struct {Name}Item by the naming of the workbook with serde derives, keyed by identifier as the json rows,
+// {Name}DefKey consts and the rows built on first use by rows(). e_{base}_{name}.rs are the enums,
+// mod.rs declares every module of the dir
+pub struct RustEmitter;
+
+impl Emitter for RustEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_RS_DIR })?;
+        for def in model.enums.iter() {
+            self.emit_enum(def)?;
+        }
+
+        let name = &model.name;
+        let item_name = naming::of(name).item_class(name);
+        let end = LINE_END_FLAG;
+        let mut uses = BTreeSet::<String>::default();
+
+        let mut fields = Vec::<u8>::default();
+        let mut types = Vec::<String>::default();
+        for column in model.columns.iter() {
+            if let Some(comment) = column.comment.as_ref() {
+                fields.write_fmt(format_args!("\t/// {}{}", comment.replace(['\r', '\n'], " "), end))?;
+            }
+            let ty = rs_type(&shape_of(&column.ty), &param_type(column, name), &mut uses);
+            fields.write_fmt(format_args!("\t#[serde(rename = \"{}\")]{}", column.ident, end))?;
+            fields.write_fmt(format_args!("\tpub {}: {},{}", ident(&snake_case(&column.ident)), ty, end))?;
+            types.push(ty);
+        }
+
+        let output_path = format!("{}/{}.rs", unsafe { OUTPUT_RS_DIR }, snake_case(name));
+        let mut file = File::create(output_path)?;
+        file.write_fmt(format_args!("// This File is generated by the program, DO NOT EDIT MANUALLY!{}{}", end, end))?;
+        file.write_fmt(format_args!("use serde::{{Deserialize, Serialize}};{}", end))?;
+        file.write_fmt(format_args!("use std::sync::OnceLock;{}", end))?;
+        for v in uses.iter() {
+            file.write_fmt(format_args!("use super::{}::{};{}", snake_case(v), v, end))?;
+        }
+
+        file.write_fmt(format_args!("{}#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]{}", end, end))?;
+        file.write_fmt(format_args!("pub struct {} {{{}", item_name, end))?;
+        file.write(&fields)?;
+        file.write_fmt(format_args!("}}{}", end))?;
+
+        let def_keys = model.def_keys();
+        if !def_keys.is_empty() {
+            file.write_fmt(format_args!("{}pub struct {}DefKey;{}{}", end, name, end, end))?;
+            file.write_fmt(format_args!("#[allow(non_upper_case_globals)]{}", end))?;
+            file.write_fmt(format_args!("impl {}DefKey {{{}", name, end))?;
+            for (key, id) in def_keys.iter() {
+                file.write_fmt(format_args!("\tpub const {}: {} = {};{}", ident(key), types[0], id, end))?;
+            }
+            file.write_fmt(format_args!("}}{}", end))?;
+        }
+
+        file.write_fmt(format_args!("{}pub fn rows() -> &'static [{}] {{{}", end, item_name, end))?;
+        file.write_fmt(format_args!("\tstatic ROWS: OnceLock<Vec<{}>> = OnceLock::new();{}", item_name, end))?;
+        file.write_fmt(format_args!("\tROWS.get_or_init(|| vec![{}", end))?;
+        for row in 0..model.lines {
+            file.write_fmt(format_args!("\t\t{} {{ ", item_name))?;
+            for (i, column) in model.columns.iter().enumerate() {
+                if i > 0 {
+                    file.write(", ".as_bytes())?;
+                }
+                file.write_fmt(format_args!("{}: ", ident(&snake_case(&column.ident))))?;
+                write_value(column.value(row), &types[i], &mut file)?;
+            }
+            file.write_fmt(format_args!(" }},{}", end))?;
+        }
+        file.write_fmt(format_args!("\t]){}}}{}", end, end))?;
+        file.flush()
+    }
+
+    fn emit_enum(&self, def: &EnumDef) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_RS_DIR })?;
+        let type_name = naming::enum_type(&def.owner, &def.name);
+        let output_path = format!("{}/{}.rs", unsafe { OUTPUT_RS_DIR }, snake_case(&type_name));
+        let mut file = File::create(output_path)?;
+        let end = LINE_END_FLAG;
+
+        file.write_fmt(format_args!("// This File is generated by the program, DO NOT EDIT MANUALLY!{}{}", end, end))?;
+        file.write_fmt(format_args!("use serde::{{Deserialize, Serialize}};{}{}", end, end))?;
+        file.write_fmt(format_args!("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]{}", end))?;
+        file.write_fmt(format_args!("#[allow(non_camel_case_types)]{}", end))?;
+        file.write_fmt(format_args!("#[repr(i32)]{}", end))?;
+        file.write_fmt(format_args!("pub enum {} {{{}", type_name, end))?;
+        for (key, val, desc) in def.members.iter() {
+            file.write_fmt(format_args!("\t/// {}{}", desc.replace(['\r', '\n'], " "), end))?;
+            file.write_fmt(format_args!("\t{} = {},{}", ident(key), val, end))?;
+        }
+        file.write_fmt(format_args!("}}{}{}", end, end))?;
+
+        // the unknown value is the error
+        file.write_fmt(format_args!("impl TryFrom<i32> for {} {{{}", type_name, end))?;
+        file.write_fmt(format_args!("\ttype Error = i32;{}{}", end, end))?;
+        file.write_fmt(format_args!("\tfn try_from(v: i32) -> Result<Self, Self::Error> {{{}", end))?;
+        file.write_fmt(format_args!("\t\tmatch v {{{}", end))?;
+        for (key, val, _) in def.members.iter() {
+            file.write_fmt(format_args!("\t\t\t{} => Ok(Self::{}),{}", val, ident(key), end))?;
+        }
+        file.write_fmt(format_args!("\t\t\t_ => Err(v),{}", end))?;
+        file.write_fmt(format_args!("\t\t}}{}", end))?;
+        file.write_fmt(format_args!("\t}}{}", end))?;
+        file.write_fmt(format_args!("}}{}", end))?;
+        file.flush()
+    }
+
+    // every module of the dir, also those of the workbooks not built this time
+    fn finish(&self) -> Result<()> {
+        let dir = unsafe { OUTPUT_RS_DIR };
+        if fs::metadata(dir).is_err() {
+            return Ok(());
+        }
+        let mut mods = BTreeSet::<String>::default();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|x| x == "rs") {
+                if let Some(stem) = path.file_stem().and_then(|v| v.to_str()).filter(|v| *v != "mod") {
+                    mods.insert(String::from(stem));
+                }
+            }
+        }
+
+        let end = LINE_END_FLAG;
+        let mut file = File::create(format!("{}/mod.rs", dir))?;
+        file.write_fmt(format_args!("// This File is generated by the program, DO NOT EDIT MANUALLY!{}{}", end, end))?;
+        for v in mods.iter() {
+            file.write_fmt(format_args!("pub mod {};{}", v, end))?;
+        }
+        file.flush()
+    }
+}
+
+// TemplateId -> template_id, HPMax -> hp_max
+fn snake_case(v: &str) -> String {
+    let chars = v.chars().collect::<Vec<char>>();
+    let mut ret = String::with_capacity(v.len() + 4);
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|v| v.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                ret.push('_');
+            }
+        }
+        ret.extend(c.to_lowercase());
+    }
+    ret
+}
+
+// keywords are raw identifiers
+fn ident(v: &str) -> String {
+    if KEYWORDS.contains(&v) {
+        format!("r#{}", v)
+    } else {
+        String::from(v)
+    }
+}
+
+// empty cells of reference types are None
+fn rs_type(shape: &Shape, ty: &str, uses: &mut BTreeSet<String>) -> String {
+    match shape {
+        Shape::Bool => String::from("bool"),
+        Shape::SByte => String::from("i8"),
+        Shape::Byte => String::from("u8"),
+        Shape::Short => String::from("i16"),
+        Shape::UShort => String::from("u16"),
+        Shape::Int => String::from("i32"),
+        Shape::UInt => String::from("u32"),
+        Shape::Float => String::from("f32"),
+        Shape::Double => String::from("f64"),
+        Shape::String | Shape::Custom => String::from("Option<String>"),
+        Shape::ShortList => String::from("Option<Vec<i16>>"),
+        Shape::Enum => {
+            uses.insert(String::from(ty));
+            format!("Option<{}>", ty)
+        }
+        Shape::Array(inner) | Shape::List(inner) => {
+            let inner_ty = match shape {
+                Shape::Array(_) => String::from(ty.strip_suffix("[]").unwrap_or(ty)),
+                _ => generic_args(ty).pop().unwrap_or_default(),
+            };
+            format!("Option<Vec<{}>>", rs_type(inner, &inner_ty, uses))
+        }
+        Shape::Tuple(shapes) | Shape::ValueTuple(shapes) => {
+            let items = shapes.iter().zip(generic_args(ty).iter()).map(|(s, t)| rs_type(s, t, uses)).collect::<Vec<String>>();
+            if items.len() == 1 {
+                format!("Option<({},)>", items[0])
+            } else {
+                format!("Option<({})>", items.join(", "))
+            }
+        }
+    }
+}
+
+// ty is the rust type of the value, the elements of vectors and tuples are written by their own types
+fn write_value<W: Write + ?Sized>(v: &CellValue, ty: &str, stream: &mut W) -> Result<()> {
+    match v {
+        CellValue::DEnum(EnumValue(name, val, owner)) => {
+            if val.is_empty() {
+                stream.write("None".as_bytes())?;
+            } else {
+                stream.write_fmt(format_args!("Some({}::{})", naming::enum_type(owner, name), ident(val)))?;
+            }
+        }
+        CellValue::DBool(BoolValue(b)) => { stream.write(b.to_string().as_bytes())?; }
+        CellValue::DLString(LStringValue(_, id)) => { stream.write(id.to_string().as_bytes())?; }
+        CellValue::DString(StringValue(s)) => {
+            let s = s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s);
+            stream.write_fmt(format_args!("Some(String::from({:?}))", s))?;
+        }
+        CellValue::DShort(ShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DUShort(UShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DSByte(SByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DByte(ByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DInt(IntValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DUInt(UIntValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DFloat(FloatValue(n)) => { stream.write(float_str(*n as f64, format!("{:?}", n), "f32").as_bytes())?; }
+        CellValue::DDouble(DoubleValue(n)) => { stream.write(float_str(*n, format!("{:?}", n), "f64").as_bytes())?; }
+        CellValue::DCustom(CustomValue(_, params)) => { stream.write_fmt(format_args!("Some(String::from({:?}))", params))?; }
+        CellValue::DShortList(ShortListValue(ArrayValue(arr))) => { write_array(arr.iter().skip(1), ty, false, stream)?; }
+        CellValue::DTuple(TupleValue(arr)) => { write_array(arr.iter(), ty, true, stream)?; }
+        CellValue::DValueTuple(ValueTupleValue(arr)) => { write_array(arr.iter(), ty, true, stream)?; }
+        CellValue::DArray(ArrayValue(arr)) => { write_array(arr.iter().skip(1), ty, false, stream)?; }
+        CellValue::DList(ListValue(arr)) => { write_array(arr.iter().skip(1), ty, false, stream)?; }
+        CellValue::DNone(NoneValue(t)) => { stream.write(super::empty_literal(t).unwrap_or("None").as_bytes())?; }
+        CellValue::DError(_) => { stream.write("None".as_bytes())?; }
+    }
+    Ok(())
+}
+
+fn write_array<'a, W: Write + ?Sized>(arr: impl Iterator<Item = &'a CellValue>, ty: &str, is_tuple: bool, stream: &mut W) -> Result<()> {
+    // Option<Vec<T>> or Option<(A, B)>
+    let inner = ty.strip_prefix("Option<").and_then(|v| v.strip_suffix('>')).unwrap_or(ty);
+    let args = if is_tuple {
+        generic_args(&format!("<{}>", inner.trim_start_matches('(').trim_end_matches(')').trim_end_matches(',')))
+    } else {
+        generic_args(inner)
+    };
+
+    stream.write(if is_tuple { "Some((" } else { "Some(vec![" }.as_bytes())?;
+    let mut count = 0;
+    for (i, v) in arr.enumerate() {
+        if i > 0 {
+            stream.write(", ".as_bytes())?;
+        }
+        let elem = if is_tuple { args.get(i) } else { args.first() };
+        write_value(v, elem.map_or("", |v| v.as_str()), stream)?;
+        count += 1;
+    }
+    if is_tuple && count == 1 {
+        stream.write(",".as_bytes())?;
+    }
+    stream.write(if is_tuple { "))" } else { "])" }.as_bytes())?;
+    Ok(())
+}
+
+// s is the Debug text of the value, which keeps the fraction
+fn float_str(v: f64, s: String, ty: &str) -> String {
+    if v.is_nan() {
+        format!("{}::NAN", ty)
+    } else if v.is_infinite() {
+        format!("{}{}::INFINITY", if v > 0.0 { "" } else { "-" }, ty)
+    } else {
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Column;
+    use std::path::Path;
+    use std::process::Command;
+    use std::rc::Rc;
+
+    fn rc(v: &str) -> Rc<String> {
+        Rc::new(String::from(v))
+    }
+
+    fn model() -> TableModel {
+        let ints = |v: &[i32]| CellValue::DList(ListValue(std::iter::once(CellValue::DInt(IntValue(0))).chain(v.iter().map(|n| CellValue::DInt(IntValue(*n)))).collect()));
+        TableModel {
+            name: String::from("Item"),
+            columns: vec![
+                Column::new("Id", "int", vec![CellValue::DInt(IntValue(1)), CellValue::DInt(IntValue(2))]),
+                Column::new("Name", "string", vec![CellValue::DString(StringValue(rc("\"sword\""))), CellValue::DNone(NoneValue(rc("string")))]),
+                Column::new("Kind", "enum", vec![CellValue::DEnum(EnumValue(rc("Kind"), rc("Weapon"), rc("Item"))), CellValue::DEnum(EnumValue(rc("Kind"), rc(""), rc("Item")))]),
+                Column::new("Type", "int", vec![CellValue::DInt(IntValue(3)), CellValue::DNone(NoneValue(rc("int")))]),
+                Column::new("Costs", "List<int>", vec![ints(&[1, -2]), ints(&[])]),
+                Column::new("Range", "ValueTuple<int,float>", vec![
+                    CellValue::DValueTuple(ValueTupleValue(vec![CellValue::DInt(IntValue(2)), CellValue::DFloat(FloatValue(0.5))])),
+                    CellValue::DValueTuple(ValueTupleValue(vec![CellValue::DInt(IntValue(0)), CellValue::DFloat(FloatValue(f32::INFINITY))])),
+                ]),
+            ],
+            lines: 2,
+            enums: vec![EnumDef {
+                owner: String::from("Item"),
+                name: String::from("Kind"),
+                members: vec![(rc("None"), 0, rc("")), (rc("Weapon"), 200, rc("main\nhand"))],
+                reserved: BTreeSet::default(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    // tests/rust_fixture is copied next to the build and uses the generated modules.
+    // it builds serde at the version of its Cargo.lock, run it with `cargo test -- --ignored` when that can be fetched
+    #[test]
+    #[ignore]
+    fn generated_module_compiles() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let dir = root.join("target").join("rust-fixture");
+        let _ = fs::remove_dir_all(dir.join("src"));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::copy(root.join("tests/rust_fixture/Cargo.toml"), dir.join("Cargo.toml")).unwrap();
+        fs::copy(root.join("tests/rust_fixture/Cargo.lock"), dir.join("Cargo.lock")).unwrap();
+        fs::copy(root.join("tests/rust_fixture/src/main.rs"), dir.join("src/main.rs")).unwrap();
+
+        unsafe {
+            OUTPUT_RS_DIR = Box::leak(dir.join("src").join("config").to_string_lossy().into_owned().into_boxed_str());
+        }
+        RustEmitter.emit(&model()).unwrap();
+        RustEmitter.finish().unwrap();
+
+        let output = Command::new(env!("CARGO"))
+            .args(["run", "--quiet", "--locked", "--manifest-path"])
+            .arg(dir.join("Cargo.toml"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::defs::{OUTPUT_DATA_DIR, LINE_END_FLAG};
+use crate::naming;
+use crate::parser::{TableModel, Column};
+use super::Emitter;
+use super::binary::{Shape, shape_of};
+use super::csharp::{param_type, generic_args};
+use super::json::{self, escape_str};
+use std::fs::{self, File};
+use std::io::{Write, Result};
+
+// {Name}.schema.json into the data dir: JSON Schema (2020-12) of the rows of {Name}.json.
+// enums are the lists of their identifiers, columns with a default are optional
+pub struct SchemaEmitter;
+
+impl Emitter for SchemaEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_DATA_DIR })?;
+        let name = &model.name;
+        let output_path = format!("{}/{}.schema.json", unsafe { OUTPUT_DATA_DIR }, name);
+        let mut file = File::create(output_path)?;
+        let end = LINE_END_FLAG;
+
+        file.write_fmt(format_args!("{{{}", end))?;
+        file.write_fmt(format_args!("\t\"$schema\": \"https://json-schema.org/draft/2020-12/schema\",{}", end))?;
+        file.write_fmt(format_args!("\t\"$id\": \"{}.schema.json\",{}", escape_str(name), end))?;
+        file.write_fmt(format_args!("\t\"title\": \"{}\",{}", escape_str(name), end))?;
+        file.write_fmt(format_args!("\t\"type\": \"array\",{}", end))?;
+        file.write_fmt(format_args!("\t\"items\": {{{}", end))?;
+        file.write_fmt(format_args!("\t\t\"type\": \"object\",{}", end))?;
+        file.write_fmt(format_args!("\t\t\"properties\": {{{}", end))?;
+        for (i, column) in model.columns.iter().enumerate() {
+            file.write_fmt(format_args!("\t\t\t\"{}\": {}", escape_str(&column.ident), column_schema(column, model)?))?;
+            file.write_fmt(format_args!("{}{}", if i + 1 < model.columns.len() { "," } else { "" }, end))?;
+        }
+        file.write_fmt(format_args!("\t\t}},{}", end))?;
+
+        let required = model.columns.iter().filter(|v| v.default.is_none())
+            .map(|v| format!("\"{}\"", escape_str(&v.ident)))
+            .collect::<Vec<String>>();
+        file.write_fmt(format_args!("\t\t\"required\": [{}],{}", required.join(", "), end))?;
+        file.write_fmt(format_args!("\t\t\"additionalProperties\": false{}", end))?;
+        file.write_fmt(format_args!("\t}}{}", end))?;
+        file.write_fmt(format_args!("}}{}", end))?;
+        file.flush()
+    }
+}
+
+// comment, type, default and foreign key of the column in one line
+fn column_schema(column: &Column, model: &TableModel) -> Result<String> {
+    let mut members = Vec::<String>::default();
+    if let Some(comment) = column.comment.as_ref() {
+        members.push(format!("\"description\": \"{}\"", escape_str(comment)));
+    }
+
+    let shape = shape_of(&column.ty);
+    let ty = param_type(column, &model.name);
+    let body = type_schema(&shape, &ty, model);
+    members.push(String::from(&body[1..body.len() - 1]));
+    if let (Shape::Array(_), Some(len)) = (&shape, column.fixed_len) {
+        members.push(format!("\"minItems\": {}, \"maxItems\": {}", len, len));
+    }
+
+    if let Some(default) = column.default.as_ref() {
+        let mut buf = Vec::<u8>::default();
+        json::write_value(default, &mut buf)?;
+        members.push(format!("\"default\": {}", String::from_utf8_lossy(&buf)));
+    }
+    // the values are the rows of the target, the pattern tells how they were looked up
+    if let Some(fk) = column.fk.as_ref() {
+        match column.fk_table() {
+            Some(table) => members.push(format!("\"x-foreign-key\": {{\"table\": \"{}\", \"pattern\": \"*{}\"}}", escape_str(table), escape_str(fk))),
+            None => members.push(format!("\"x-foreign-key\": {{\"pattern\": \"*{}\"}}", escape_str(fk))),
+        }
+    }
+    Ok(format!("{{{}}}", members.join(", ")))
+}
+
+// empty cells of reference types are null
+fn type_schema(shape: &Shape, ty: &str, model: &TableModel) -> String {
+    let int = |min: i64, max: i64| format!("{{\"type\": \"integer\", \"minimum\": {}, \"maximum\": {}}}", min, max);
+    match shape {
+        Shape::Bool => String::from("{\"type\": \"boolean\"}"),
+        Shape::SByte => int(i8::MIN as i64, i8::MAX as i64),
+        Shape::Byte => int(0, u8::MAX as i64),
+        Shape::Short => int(i16::MIN as i64, i16::MAX as i64),
+        Shape::UShort => int(0, u16::MAX as i64),
+        Shape::Int => int(i32::MIN as i64, i32::MAX as i64),
+        Shape::UInt => int(0, u32::MAX as i64),
+        Shape::Float | Shape::Double => String::from("{\"type\": \"number\"}"),
+        Shape::String | Shape::Custom => String::from("{\"type\": [\"string\", \"null\"]}"),
+        Shape::ShortList => format!("{{\"type\": [\"array\", \"null\"], \"items\": {}}}", int(i16::MIN as i64, i16::MAX as i64)),
+        Shape::Enum => {
+            let def = model.enums.iter().chain(model.enum_refs.iter()).find(|v| naming::enum_type(&v.owner, &v.name) == ty);
+            let mut idents = def.map(|v| v.members.iter().map(|m| format!("\"{}\"", escape_str(&m.0))).collect::<Vec<String>>()).unwrap_or_default();
+            idents.push(String::from("null"));
+            format!("{{\"enum\": [{}]}}", idents.join(", "))
+        }
+        Shape::Array(inner) | Shape::List(inner) => {
+            let inner_ty = match shape {
+                Shape::Array(_) => String::from(ty.strip_suffix("[]").unwrap_or(ty)),
+                _ => generic_args(ty).pop().unwrap_or_default(),
+            };
+            format!("{{\"type\": [\"array\", \"null\"], \"items\": {}}}", type_schema(inner, &inner_ty, model))
+        }
+        Shape::Tuple(shapes) | Shape::ValueTuple(shapes) => {
+            let items = shapes.iter().zip(generic_args(ty).iter()).map(|(s, t)| type_schema(s, t, model)).collect::<Vec<String>>();
+            format!("{{\"type\": [\"array\", \"null\"], \"prefixItems\": [{}], \"items\": false, \"minItems\": {}, \"maxItems\": {}}}", items.join(", "), items.len(), items.len())
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::defs::{OUTPUT_DATA_DIR, SQLITE_DB_NAME};
+use crate::naming;
+use crate::parser::cell_value::*;
+use crate::parser::{TableModel, EnumDef, Column};
+use super::Emitter;
+use super::binary::{Shape, shape_of};
+use super::csharp::param_type;
+use rusqlite::{Connection, TransactionBehavior, types::Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::time::Duration;
+
+// {OUTPUT_DATA_DIR}/Config.sqlite: one table per workbook, one per enum and LString(id, text).
+// empty cells are NULL, lists and tuples are json text, enums their values
+pub struct SqliteEmitter;
+
+impl Emitter for SqliteEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        let mut conn = open()?;
+        // workbooks of other dirs write the same file at the same time
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate).map_err(sql_err)?;
+        for def in model.enums.iter() {
+            write_enum(&tx, def)?;
+        }
+
+        let name = quote(&model.name);
+        let mut defs = Vec::<String>::default();
+        for (i, column) in model.columns.iter().enumerate() {
+            defs.push(format!("{} {}", quote(&column.ident), column_def(column, &model.name, i == 0)));
+        }
+        tx.execute_batch(&format!("DROP TABLE IF EXISTS {};\nCREATE TABLE {} ({});", name, name, defs.join(", "))).map_err(sql_err)?;
+
+        let holders = vec!["?"; model.columns.len()].join(", ");
+        let mut lstrings = BTreeMap::<i32, String>::default();
+        {
+            let mut stmt = tx.prepare(&format!("INSERT INTO {} VALUES ({})", name, holders)).map_err(sql_err)?;
+            for row in 0..model.lines {
+                let mut vals = Vec::<Value>::with_capacity(model.columns.len());
+                for (i, column) in model.columns.iter().enumerate() {
+                    // the first column is the row index, as in the generated classes
+                    if i == 0 {
+                        vals.push(Value::Integer(row as i64));
+                    } else {
+                        vals.push(sql_value(column.value(row), model, &mut lstrings)?);
+                    }
+                }
+                stmt.execute(rusqlite::params_from_iter(vals.iter())).map_err(sql_err)?;
+            }
+        }
+
+        tx.execute_batch("CREATE TABLE IF NOT EXISTS LString (id INTEGER PRIMARY KEY, text TEXT);").map_err(sql_err)?;
+        for (id, text) in lstrings.iter() {
+            tx.execute("INSERT OR REPLACE INTO LString VALUES (?, ?)", rusqlite::params![id, text]).map_err(sql_err)?;
+        }
+        tx.commit().map_err(sql_err)
+    }
+
+    fn emit_enum(&self, def: &EnumDef) -> Result<()> {
+        let mut conn = open()?;
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate).map_err(sql_err)?;
+        write_enum(&tx, def)?;
+        tx.commit().map_err(sql_err)
+    }
+}
+
+fn open() -> Result<Connection> {
+    fs::create_dir_all(unsafe { OUTPUT_DATA_DIR })?;
+    let conn = Connection::open(format!("{}/{}", unsafe { OUTPUT_DATA_DIR }, SQLITE_DB_NAME)).map_err(sql_err)?;
+    conn.busy_timeout(Duration::from_secs(60)).map_err(sql_err)?;
+    // tables are written in any order, the constraints are checked by the readers
+    conn.pragma_update(None, "foreign_keys", false).map_err(sql_err)?;
+    Ok(conn)
+}
+
+fn sql_err(e: rusqlite::Error) -> Error {
+    Error::new(ErrorKind::Other, e)
+}
+
+fn quote(v: &str) -> String {
+    format!("\"{}\"", v.replace('"', "\"\""))
+}
+
+fn write_enum(conn: &Connection, def: &EnumDef) -> Result<()> {
+    let name = quote(&naming::enum_type(&def.owner, &def.name));
+    conn.execute_batch(&format!("DROP TABLE IF EXISTS {};\nCREATE TABLE {} (value INTEGER PRIMARY KEY, ident TEXT, desc TEXT);", name, name)).map_err(sql_err)?;
+    let mut stmt = conn.prepare(&format!("INSERT INTO {} VALUES (?, ?, ?)", name)).map_err(sql_err)?;
+    for (ident, val, desc) in def.members.iter() {
+        stmt.execute(rusqlite::params![val, ident.as_str(), desc.as_str()]).map_err(sql_err)?;
+    }
+    Ok(())
+}
+
+// type and constraint of the column
+fn column_def(column: &Column, name: &str, is_key: bool) -> String {
+    if is_key {
+        return String::from("INTEGER PRIMARY KEY");
+    }
+    let shape = shape_of(&column.ty);
+    match shape {
+        Shape::Enum => format!("INTEGER REFERENCES {}(value)", quote(&param_type(column, name))),
+        Shape::Int if column.ty.as_str() == "LString" || column.ty.as_str() == "Lstring" => String::from("INTEGER REFERENCES LString(id)"),
+        Shape::Bool | Shape::SByte | Shape::Byte | Shape::Short | Shape::UShort | Shape::Int | Shape::UInt => {
+            match column.fk_table() {
+                Some(target) => format!("INTEGER REFERENCES {}", quote(target)),
+                None => String::from("INTEGER"),
+            }
+        }
+        Shape::Float | Shape::Double => String::from("REAL"),
+        _ => String::from("TEXT"),
+    }
+}
+
+fn sql_value(v: &CellValue, model: &TableModel, lstrings: &mut BTreeMap<i32, String>) -> Result<Value> {
+    let ret = match v {
+        CellValue::DEnum(EnumValue(name, ident, owner)) => {
+            model.enum_value(owner, name, ident).map(|v| Value::Integer(v as i64)).unwrap_or(Value::Null)
+        }
+        CellValue::DBool(BoolValue(b)) => Value::Integer(*b as i64),
+        CellValue::DLString(LStringValue(text, id)) => {
+            if *id >= 0 {
+                lstrings.insert(*id, text.to_string());
+            }
+            Value::Integer(*id as i64)
+        }
+        CellValue::DString(StringValue(s)) => {
+            let s = s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s);
+            Value::Text(String::from(s))
+        }
+        CellValue::DShort(ShortValue(n)) => Value::Integer(*n as i64),
+        CellValue::DUShort(UShortValue(n)) => Value::Integer(*n as i64),
+        CellValue::DSByte(SByteValue(n)) => Value::Integer(*n as i64),
+        CellValue::DByte(ByteValue(n)) => Value::Integer(*n as i64),
+        CellValue::DInt(IntValue(n)) => Value::Integer(*n as i64),
+        CellValue::DUInt(UIntValue(n)) => Value::Integer(*n as i64),
+        CellValue::DFloat(FloatValue(n)) => Value::Real(*n as f64),
+        CellValue::DDouble(DoubleValue(n)) => Value::Real(*n),
+        CellValue::DCustom(CustomValue(_, params)) => Value::Text(params.to_string()),
+        CellValue::DShortList(_) | CellValue::DTuple(_) | CellValue::DValueTuple(_) | CellValue::DArray(_) | CellValue::DList(_) => {
+            let mut buf = Vec::<u8>::default();
+            super::json::write_value(v, &mut buf)?;
+            Value::Text(String::from_utf8_lossy(&buf).into_owned())
+        }
+        CellValue::DNone(_) | CellValue::DError(_) => Value::Null,
+    };
+    Ok(ret)
+}
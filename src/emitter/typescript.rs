@@ -0,0 +1,208 @@
+use crate::defs::{OUTPUT_TS_DIR, LINE_END_FLAG, GEN_TS_JSON};
+use crate::naming;
+use crate::parser::cell_value::*;
+use crate::parser::{TableModel, EnumDef};
+use super::Emitter;
+use super::binary::{Shape, shape_of};
+use super::csharp::{param_type, generic_args};
+use super::json::escape_str;
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{Write, Result};
+
+// {Name}.ts into the ts dir: interface {Name}Item by the naming of the workbook, {Name}DefKey and the rows as {Name};
+// with ts-json {Name}.d.ts and {Name}.json instead, where the enums are numbers.
+// E{Base}{Name}.ts are const enums
+pub struct TsEmitter;
+
+impl Emitter for TsEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_TS_DIR })?;
+        for def in model.enums.iter() {
+            self.emit_enum(def)?;
+        }
+
+        let name = &model.name;
+        let item_name = naming::of(name).item_class(name);
+        let end = LINE_END_FLAG;
+        let json = unsafe { GEN_TS_JSON };
+        let mut imports = BTreeSet::<String>::default();
+
+        let mut fields = Vec::<u8>::default();
+        for column in model.columns.iter() {
+            if let Some(comment) = column.comment.as_ref() {
+                fields.write_fmt(format_args!("\t/** {} */{}", comment.replace("*/", "* /"), end))?;
+            }
+            let ty = ts_type(&shape_of(&column.ty), &param_type(column, name), &mut imports);
+            fields.write_fmt(format_args!("\treadonly {}: {};{}", column.ident, ty, end))?;
+        }
+
+        // a module left behind by the other mode would shadow this one
+        let stale = format!("{}/{}.{}", unsafe { OUTPUT_TS_DIR }, name, if json { "ts" } else { "d.ts" });
+        if fs::metadata(&stale).is_ok() {
+            fs::remove_file(stale)?;
+        }
+        let dest = format!("{}/{}.{}", unsafe { OUTPUT_TS_DIR }, name, if json { "d.ts" } else { "ts" });
+        let mut file = File::create(dest)?;
+        file.write_fmt(format_args!("// This File is generated by the program, DO NOT EDIT MANUALLY!{}{}", end, end))?;
+        for v in imports.iter() {
+            file.write_fmt(format_args!("import {{ {} }} from \"./{}\";{}", v, v, end))?;
+        }
+        if !imports.is_empty() {
+            file.write(end.as_bytes())?;
+        }
+
+        file.write_fmt(format_args!("export interface {} {{{}", item_name, end))?;
+        file.write(&fields)?;
+        file.write_fmt(format_args!("}}{}", end))?;
+
+        let def_keys = model.def_keys();
+        if !def_keys.is_empty() {
+            file.write(end.as_bytes())?;
+            // declarations have no values, const enums are inlined
+            if json {
+                file.write_fmt(format_args!("export declare const enum {}DefKey {{{}", name, end))?;
+                for (key, id) in def_keys.iter() {
+                    file.write_fmt(format_args!("\t{} = {},{}", key, id, end))?;
+                }
+                file.write_fmt(format_args!("}}{}", end))?;
+            } else {
+                file.write_fmt(format_args!("export const {}DefKey = {{{}", name, end))?;
+                for (key, id) in def_keys.iter() {
+                    file.write_fmt(format_args!("\t{}: {},{}", key, id, end))?;
+                }
+                file.write_fmt(format_args!("}} as const;{}", end))?;
+            }
+        }
+
+        if json {
+            // import rows from "./{Name}.json" and cast them to ReadonlyArray<{Name}Item>
+            let dest = format!("{}/{}.json", unsafe { OUTPUT_TS_DIR }, name);
+            let mut data = File::create(dest)?;
+            write_rows(model, json, &mut data)?;
+            data.write(end.as_bytes())?;
+            data.flush()?;
+        } else {
+            file.write(end.as_bytes())?;
+            file.write_fmt(format_args!("export const {}: ReadonlyArray<{}> = ", name, item_name))?;
+            write_rows(model, json, &mut file)?;
+            file.write_fmt(format_args!(";{}", end))?;
+        }
+        file.flush()
+    }
+
+    fn emit_enum(&self, def: &EnumDef) -> Result<()> {
+        fs::create_dir_all(unsafe { OUTPUT_TS_DIR })?;
+        let type_name = naming::enum_type(&def.owner, &def.name);
+        let dest = format!("{}/{}.ts", unsafe { OUTPUT_TS_DIR }, type_name);
+        let mut file = File::create(dest)?;
+        let end = LINE_END_FLAG;
+
+        file.write_fmt(format_args!("// This File is generated by the program, DO NOT EDIT MANUALLY!{}{}", end, end))?;
+        file.write_fmt(format_args!("export const enum {} {{{}", type_name, end))?;
+        for (ident, val, desc) in def.members.iter() {
+            file.write_fmt(format_args!("\t/** {} */{}", desc.replace("*/", "* /"), end))?;
+            file.write_fmt(format_args!("\t{} = {},{}", ident, val, end))?;
+        }
+        file.write_fmt(format_args!("}}{}", end))?;
+        file.flush()
+    }
+}
+
+// empty cells of reference types are null
+fn ts_type(shape: &Shape, ty: &str, imports: &mut BTreeSet<String>) -> String {
+    match shape {
+        Shape::Bool => String::from("boolean"),
+        Shape::String | Shape::Custom => String::from("string | null"),
+        Shape::ShortList => String::from("readonly number[] | null"),
+        Shape::Enum => {
+            imports.insert(String::from(ty));
+            format!("{} | null", ty)
+        }
+        Shape::Array(inner) | Shape::List(inner) => {
+            let inner_ty = match shape {
+                Shape::Array(_) => String::from(ty.strip_suffix("[]").unwrap_or(ty)),
+                _ => generic_args(ty).pop().unwrap_or_default(),
+            };
+            format!("ReadonlyArray<{}> | null", ts_type(inner, &inner_ty, imports))
+        }
+        Shape::Tuple(shapes) | Shape::ValueTuple(shapes) => {
+            let items = shapes.iter().zip(generic_args(ty).iter()).map(|(s, t)| ts_type(s, t, imports)).collect::<Vec<String>>();
+            format!("readonly [{}] | null", items.join(", "))
+        }
+        _ => String::from("number"),
+    }
+}
+
+fn write_rows<W: Write + ?Sized>(model: &TableModel, json: bool, stream: &mut W) -> Result<()> {
+    let end = LINE_END_FLAG;
+    stream.write("[".as_bytes())?;
+    for row in 0..model.lines {
+        stream.write_fmt(format_args!("{}\t{{ ", end))?;
+        for (i, column) in model.columns.iter().enumerate() {
+            if i > 0 {
+                stream.write(", ".as_bytes())?;
+            }
+            if json {
+                stream.write_fmt(format_args!("\"{}\": ", column.ident))?;
+            } else {
+                stream.write_fmt(format_args!("{}: ", column.ident))?;
+            }
+            write_value(column.value(row), model, json, stream)?;
+        }
+        stream.write(" }".as_bytes())?;
+        if row + 1 < model.lines {
+            stream.write(",".as_bytes())?;
+        }
+    }
+    stream.write_fmt(format_args!("{}]", end))?;
+    Ok(())
+}
+
+// enums are members of the const enums in .ts, their values in json
+fn write_value<W: Write + ?Sized>(v: &CellValue, model: &TableModel, json: bool, stream: &mut W) -> Result<()> {
+    match v {
+        CellValue::DEnum(EnumValue(name, val, owner)) => {
+            match model.enum_value(owner, name, val) {
+                Some(n) if json => { stream.write(n.to_string().as_bytes())?; }
+                Some(_) => { stream.write_fmt(format_args!("{}.{}", naming::enum_type(owner, name), val))?; }
+                None => { stream.write("null".as_bytes())?; }
+            }
+        }
+        CellValue::DBool(BoolValue(b)) => { stream.write(b.to_string().as_bytes())?; }
+        CellValue::DLString(LStringValue(_, id)) => { stream.write(id.to_string().as_bytes())?; }
+        CellValue::DString(StringValue(s)) => {
+            let s = s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s);
+            stream.write_fmt(format_args!("\"{}\"", escape_str(s)))?;
+        }
+        CellValue::DShort(ShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DUShort(UShortValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DSByte(SByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DByte(ByteValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DInt(IntValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DUInt(UIntValue(n)) => { stream.write(n.to_string().as_bytes())?; }
+        CellValue::DFloat(FloatValue(n)) => { stream.write(if n.is_finite() { n.to_string() } else { String::from("null") }.as_bytes())?; }
+        CellValue::DDouble(DoubleValue(n)) => { stream.write(if n.is_finite() { n.to_string() } else { String::from("null") }.as_bytes())?; }
+        CellValue::DCustom(CustomValue(_, params)) => { stream.write_fmt(format_args!("\"{}\"", escape_str(params)))?; }
+        CellValue::DShortList(ShortListValue(ArrayValue(arr))) => { write_array(arr.iter().skip(1), model, json, stream)?; }
+        CellValue::DTuple(TupleValue(arr)) => { write_array(arr.iter(), model, json, stream)?; }
+        CellValue::DValueTuple(ValueTupleValue(arr)) => { write_array(arr.iter(), model, json, stream)?; }
+        CellValue::DArray(ArrayValue(arr)) => { write_array(arr.iter().skip(1), model, json, stream)?; }
+        CellValue::DList(ListValue(arr)) => { write_array(arr.iter().skip(1), model, json, stream)?; }
+        CellValue::DNone(NoneValue(ty)) => { stream.write(super::empty_literal(ty).unwrap_or("null").as_bytes())?; }
+        CellValue::DError(_) => { stream.write("null".as_bytes())?; }
+    }
+    Ok(())
+}
+
+fn write_array<'a, W: Write + ?Sized>(arr: impl Iterator<Item = &'a CellValue>, model: &TableModel, json: bool, stream: &mut W) -> Result<()> {
+    stream.write("[".as_bytes())?;
+    for (i, v) in arr.enumerate() {
+        if i > 0 {
+            stream.write(", ".as_bytes())?;
+        }
+        write_value(v, model, json, stream)?;
+    }
+    stream.write("]".as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,493 @@
+use crate::defs::{OUTPUT_UNITY_DIR, UNITY_TABLES, LINE_END_FLAG};
+use crate::naming;
+use crate::parser::cell_value::*;
+use crate::parser::{TableModel, Column};
+use super::Emitter;
+use super::binary::{Shape, shape_of};
+use super::csharp::{param_type, generic_args};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, Write, Result};
+
+lazy_static! (
+    // type name -> workbook, the helpers share the namespace with the classes of every table
+    static ref TYPE_OWNERS: DashMap<String, String> = DashMap::default();
+);
+
+// a [Serializable] type the fields need, Unity serializes neither tuples nor lists of lists
+struct Helper {
+    name: String,
+    is_struct: bool,
+    fields: Vec<(String, String)>, // (type, ident)
+}
+
+// {Name}Asset.cs into the unity dir: ScriptableObject {Name}Asset with the rows as List<{Name}Entry>,
+// {Name}.asset its instance in Unity YAML, .meta of both with GUIDs hashed from the file names.
+// enums are those of the enum dir, written as their values, -1 if empty. strings are never null in Unity,
+// empty ones are "", empty lists are []
+pub struct UnityEmitter;
+
+impl Emitter for UnityEmitter {
+    fn emit(&self, model: &TableModel) -> Result<()> {
+        let tables = unsafe { UNITY_TABLES };
+        if !tables.is_empty() && !tables.split(',').any(|v| v.trim() == model.name) {
+            // the classes of the table are still taken
+            return claim_names(model, None);
+        }
+        fs::create_dir_all(unsafe { OUTPUT_UNITY_DIR })?;
+
+        let name = &model.name;
+        let mut helpers = Vec::<Helper>::default();
+        let mut fields = Vec::<(String, &Column)>::default();
+        for column in model.columns.iter() {
+            let ty = unity_type(&shape_of(&column.ty), &param_type(column, name), &format!("{}{}", name, column.ident), &naming::of(name).item_suffix, &mut helpers);
+            fields.push((ty, column));
+        }
+        claim_names(model, Some(&helpers))?;
+
+        let script = format!("{}Asset.cs", name);
+        write_script(&format!("{}/{}", unsafe { OUTPUT_UNITY_DIR }, script), name, &naming::enum_usings(model), &fields, &helpers)?;
+        write_meta(&script, "MonoImporter", &[
+            "  externalObjects: {}",
+            "  serializedVersion: 2",
+            "  defaultReferences: []",
+            "  executionOrder: 0",
+            "  icon: {instanceID: 0}",
+        ])?;
+
+        let asset = format!("{}.asset", name);
+        write_asset(&format!("{}/{}", unsafe { OUTPUT_UNITY_DIR }, asset), model, &guid(&script))?;
+        write_meta(&asset, "NativeFormatImporter", &[
+            "  externalObjects: {}",
+            "  mainObjectFileID: 11400000",
+        ])
+    }
+}
+
+// the helpers are named after the table and the column: QuestRewardItem of the tuple column RewardItem
+// and of the nested list Reward are the same, QuestReward is also the class of the table QuestReward.
+// helpers is None when the table is not exported to unity
+fn claim_names(model: &TableModel, helpers: Option<&Vec<Helper>>) -> Result<()> {
+    let name = &model.name;
+    let naming = naming::of(name);
+    let mut names = vec![naming.table_class(name), naming.item_class(name)];
+    names.extend(model.enums.iter().map(|v| naming::enum_type(&v.owner, &v.name)));
+    if let Some(helpers) = helpers {
+        names.push(format!("{}Entry", name));
+        names.push(format!("{}Asset", name));
+        names.extend(helpers.iter().map(|v| v.name.clone()));
+    }
+
+    let mut seen = HashSet::<&str>::default();
+    for v in names.iter() {
+        if !seen.insert(v.as_str()) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("{}: unity type {} is generated twice, rename the column", name, v)));
+        }
+        if let Some(owner) = TYPE_OWNERS.insert(v.clone(), name.clone()).filter(|owner| owner != name) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("{}: unity type {} is also a type of {}.xlsx, rename the column", name, v, owner)));
+        }
+    }
+    Ok(())
+}
+
+fn write_script(dest: &str, name: &str, usings: &Vec<String>, fields: &Vec<(String, &Column)>, helpers: &Vec<Helper>) -> Result<()> {
+    let end = LINE_END_FLAG;
+    let mut file = File::create(dest)?;
+    file.write_fmt(format_args!("////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////{}", end))?;
+    file.write_fmt(format_args!("// This File is generated by the program, DO NOT EDIT MANUALLY!{}", end))?;
+    file.write_fmt(format_args!("// 此文件由程序生成, 切勿手动编辑!{}", end))?;
+    file.write_fmt(format_args!("////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////{}", end))?;
+    file.write_fmt(format_args!("using System;{}", end))?;
+    file.write_fmt(format_args!("using System.Collections.Generic;{}", end))?;
+    file.write_fmt(format_args!("using UnityEngine;{}", end))?;
+    for v in usings.iter() {
+        file.write_fmt(format_args!("using {};{}", v, end))?;
+    }
+    file.write(end.as_bytes())?;
+    file.write_fmt(format_args!("#pragma warning disable 1591{}{}", end, end))?;
+    file.write_fmt(format_args!("namespace {}{}{{{}", naming::of(name).namespace, end, end))?;
+
+    file.write_fmt(format_args!("\t[Serializable]{}", end))?;
+    file.write_fmt(format_args!("\tpublic class {}Entry{}\t{{{}", name, end, end))?;
+    for (ty, column) in fields.iter() {
+        if let Some(comment) = column.comment.as_ref() {
+            file.write_fmt(format_args!("\t\t/// <summary>{}", end))?;
+            file.write_fmt(format_args!("\t\t/// {}{}", comment.replace(['\r', '\n'], " "), end))?;
+            file.write_fmt(format_args!("\t\t/// </summary>{}", end))?;
+        }
+        file.write_fmt(format_args!("\t\tpublic {} {};{}", ty, column.ident, end))?;
+    }
+    file.write_fmt(format_args!("\t}}{}", end))?;
+
+    for helper in helpers.iter() {
+        file.write_fmt(format_args!("{}\t[Serializable]{}", end, end))?;
+        file.write_fmt(format_args!("\tpublic {} {}{}\t{{{}", if helper.is_struct { "struct" } else { "class" }, helper.name, end, end))?;
+        for (ty, ident) in helper.fields.iter() {
+            file.write_fmt(format_args!("\t\tpublic {} {};{}", ty, ident, end))?;
+        }
+        file.write_fmt(format_args!("\t}}{}", end))?;
+    }
+
+    file.write_fmt(format_args!("{}\t[CreateAssetMenu(menuName = \"Config/{}\", fileName = \"{}\")]{}", end, name, name, end))?;
+    file.write_fmt(format_args!("\tpublic class {}Asset : ScriptableObject{}\t{{{}", name, end, end))?;
+    file.write_fmt(format_args!("\t\tpublic List<{}Entry> Items = new List<{}Entry>();{}", name, name, end))?;
+    file.write_fmt(format_args!("\t}}{}", end))?;
+    file.write_fmt(format_args!("}}{}", end))?;
+    file.flush()
+}
+
+fn write_asset(dest: &str, model: &TableModel, script_guid: &str) -> Result<()> {
+    let end = LINE_END_FLAG;
+    let mut file = File::create(dest)?;
+    for line in asset_lines(model, script_guid).iter() {
+        file.write_fmt(format_args!("{}{}", line, end))?;
+    }
+    file.flush()
+}
+
+fn asset_lines(model: &TableModel, script_guid: &str) -> Vec<String> {
+    let mut lines = vec![
+        String::from("%YAML 1.1"),
+        String::from("%TAG !u! tag:unity3d.com,2011:"),
+        String::from("--- !u!114 &11400000"),
+        String::from("MonoBehaviour:"),
+        String::from("  m_ObjectHideFlags: 0"),
+        String::from("  m_CorrespondingSourceObject: {fileID: 0}"),
+        String::from("  m_PrefabInstance: {fileID: 0}"),
+        String::from("  m_PrefabAsset: {fileID: 0}"),
+        String::from("  m_GameObject: {fileID: 0}"),
+        String::from("  m_Enabled: 1"),
+        String::from("  m_EditorHideFlags: 0"),
+        format!("  m_Script: {{fileID: 11500000, guid: {}, type: 3}}", script_guid),
+        format!("  m_Name: {}", model.name),
+        String::from("  m_EditorClassIdentifier: "),
+    ];
+
+    if model.lines == 0 {
+        lines.push(String::from("  Items: []"));
+    } else {
+        lines.push(String::from("  Items:"));
+        let shapes = model.columns.iter().map(|v| shape_of(&v.ty)).collect::<Vec<Shape>>();
+        for row in 0..model.lines {
+            let start = lines.len();
+            for (shape, column) in shapes.iter().zip(model.columns.iter()) {
+                write_field(&mut lines, 4, &column.ident, shape, column.value(row), model);
+            }
+            lines[start].replace_range(2..4, "- ");
+        }
+    }
+    lines
+}
+
+fn write_meta(file_name: &str, importer: &str, settings: &[&str]) -> Result<()> {
+    let end = LINE_END_FLAG;
+    let mut file = File::create(format!("{}/{}.meta", unsafe { OUTPUT_UNITY_DIR }, file_name))?;
+    file.write_fmt(format_args!("fileFormatVersion: 2{}", end))?;
+    file.write_fmt(format_args!("guid: {}{}", guid(file_name), end))?;
+    file.write_fmt(format_args!("{}:{}", importer, end))?;
+    for v in settings.iter() {
+        file.write_fmt(format_args!("{}{}", v, end))?;
+    }
+    file.write_fmt(format_args!("  userData: {}", end))?;
+    file.write_fmt(format_args!("  assetBundleName: {}", end))?;
+    file.write_fmt(format_args!("  assetBundleVariant: {}", end))?;
+    file.flush()
+}
+
+// FNV-1a 128 of the file name, the same on every run and machine
+fn guid(file_name: &str) -> String {
+    let mut hash: u128 = 0x6c62272e07bb014262b821756295c58d;
+    for b in file_name.bytes() {
+        hash ^= b as u128;
+        hash = hash.wrapping_mul(0x0000000001000000000000000000013b);
+    }
+    format!("{:032x}", hash)
+}
+
+// name is the one of the helper it may need, suffix that of its elements, Item by default
+fn unity_type(shape: &Shape, ty: &str, name: &str, suffix: &str, helpers: &mut Vec<Helper>) -> String {
+    match shape {
+        Shape::Bool => String::from("bool"),
+        Shape::SByte => String::from("sbyte"),
+        Shape::Byte => String::from("byte"),
+        Shape::Short => String::from("short"),
+        Shape::UShort => String::from("ushort"),
+        Shape::Int => String::from("int"),
+        Shape::UInt => String::from("uint"),
+        Shape::Float => String::from("float"),
+        Shape::Double => String::from("double"),
+        Shape::String | Shape::Custom => String::from("string"),
+        Shape::ShortList => String::from("List<short>"),
+        Shape::Enum => String::from(ty),
+        Shape::Array(inner) | Shape::List(inner) => {
+            let inner_ty = match shape {
+                Shape::Array(_) => String::from(ty.strip_suffix("[]").unwrap_or(ty)),
+                _ => generic_args(ty).pop().unwrap_or_default(),
+            };
+            let elem_name = format!("{}{}", name, suffix);
+            let elem = if is_collection(inner) {
+                // List<List<T>> -> List<{Name}Item> with the inner list as Items
+                let items = unity_type(inner, &inner_ty, &elem_name, suffix, helpers);
+                helpers.push(Helper { name: elem_name.clone(), is_struct: false, fields: vec![(items, String::from("Items"))] });
+                elem_name
+            } else {
+                unity_type(inner, &inner_ty, &elem_name, suffix, helpers)
+            };
+            match shape {
+                Shape::Array(_) => format!("{}[]", elem),
+                _ => format!("List<{}>", elem),
+            }
+        }
+        Shape::Tuple(shapes) | Shape::ValueTuple(shapes) => {
+            let args = generic_args(ty);
+            let mut fields = Vec::<(String, String)>::default();
+            for (i, (s, t)) in shapes.iter().zip(args.iter()).enumerate() {
+                fields.push((unity_type(s, t, &format!("{}{}{}", name, suffix, i + 1), suffix, helpers), format!("Item{}", i + 1)));
+            }
+            helpers.push(Helper { name: String::from(name), is_struct: true, fields });
+            String::from(name)
+        }
+    }
+}
+
+fn is_collection(shape: &Shape) -> bool {
+    matches!(shape, Shape::Array(_) | Shape::List(_) | Shape::ShortList)
+}
+
+// written as little-endian hex by Unity, like its own arrays of numbers
+fn is_blittable(shape: &Shape) -> bool {
+    !matches!(shape, Shape::String | Shape::Custom | Shape::Array(_) | Shape::List(_) | Shape::ShortList | Shape::Tuple(_) | Shape::ValueTuple(_))
+}
+
+// key: value at indent, nested values below it
+fn write_field(lines: &mut Vec<String>, indent: usize, key: &str, shape: &Shape, v: &CellValue, model: &TableModel) {
+    let pad = " ".repeat(indent);
+    match shape {
+        Shape::Array(_) | Shape::List(_) | Shape::ShortList => {
+            let inner = match shape {
+                Shape::Array(inner) | Shape::List(inner) => inner.as_ref(),
+                _ => &Shape::Short,
+            };
+            let elems = match v {
+                CellValue::DArray(ArrayValue(arr)) | CellValue::DList(ListValue(arr)) | CellValue::DShortList(ShortListValue(ArrayValue(arr))) => arr.iter().skip(1).collect(),
+                _ => Vec::default(),
+            };
+            if elems.is_empty() {
+                lines.push(format!("{}{}: []", pad, key));
+            } else if is_blittable(inner) {
+                let hex = elems.iter().map(|v| hex_bytes(inner, v, model)).collect::<String>();
+                lines.push(format!("{}{}: {}", pad, key, hex));
+            } else {
+                lines.push(format!("{}{}:", pad, key));
+                for elem in elems.iter() {
+                    write_elem(lines, indent, inner, elem, model);
+                }
+            }
+        }
+        Shape::Tuple(shapes) | Shape::ValueTuple(shapes) => {
+            lines.push(format!("{}{}:", pad, key));
+            write_tuple(lines, indent + 2, shapes, v, model);
+        }
+        _ => lines.push(format!("{}{}: {}", pad, key, scalar(shape, v, model))),
+    }
+}
+
+// one "- " item of a sequence at indent
+fn write_elem(lines: &mut Vec<String>, indent: usize, shape: &Shape, v: &CellValue, model: &TableModel) {
+    let pad = " ".repeat(indent);
+    let start = lines.len();
+    match shape {
+        Shape::Tuple(shapes) | Shape::ValueTuple(shapes) => write_tuple(lines, indent + 2, shapes, v, model),
+        Shape::Array(_) | Shape::List(_) | Shape::ShortList => write_field(lines, indent + 2, "Items", shape, v, model),
+        _ => {
+            lines.push(format!("{}- {}", pad, scalar(shape, v, model)));
+            return;
+        }
+    }
+    lines[start].replace_range(indent..indent + 2, "- ");
+}
+
+fn write_tuple(lines: &mut Vec<String>, indent: usize, shapes: &Vec<Shape>, v: &CellValue, model: &TableModel) {
+    let none = CellValue::DError(ErrorValue);
+    let items = match v {
+        CellValue::DTuple(TupleValue(arr)) | CellValue::DValueTuple(ValueTupleValue(arr)) => arr.iter().collect(),
+        _ => Vec::default(),
+    };
+    for (i, shape) in shapes.iter().enumerate() {
+        write_field(lines, indent, &format!("Item{}", i + 1), shape, items.get(i).copied().unwrap_or(&none), model);
+    }
+}
+
+// the value as an integer, float or text; empty cells and mismatches as the C# literals of empty cells
+fn scalar(shape: &Shape, v: &CellValue, model: &TableModel) -> String {
+    match (shape, v) {
+        (Shape::Bool, CellValue::DBool(BoolValue(b))) => String::from(if *b { "1" } else { "0" }),
+        (Shape::SByte, CellValue::DSByte(SByteValue(n))) => n.to_string(),
+        (Shape::Byte, CellValue::DByte(ByteValue(n))) => n.to_string(),
+        (Shape::Short, CellValue::DShort(ShortValue(n))) => n.to_string(),
+        (Shape::UShort, CellValue::DUShort(UShortValue(n))) => n.to_string(),
+        (Shape::Int, CellValue::DInt(IntValue(n))) => n.to_string(),
+        (Shape::Int, CellValue::DLString(LStringValue(_, id))) => id.to_string(),
+        (Shape::UInt, CellValue::DUInt(UIntValue(n))) => n.to_string(),
+        (Shape::Float, CellValue::DFloat(FloatValue(n))) => float_str(*n as f64, n.to_string()),
+        (Shape::Double, CellValue::DDouble(DoubleValue(n))) => float_str(*n, n.to_string()),
+        (Shape::String, CellValue::DString(StringValue(s))) => yaml_str(s.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(s)),
+        (Shape::Custom, CellValue::DCustom(CustomValue(_, params))) => yaml_str(params),
+        (Shape::Enum, CellValue::DEnum(EnumValue(name, val, owner))) => model.enum_value(owner, name, val).unwrap_or(-1).to_string(),
+        (Shape::Bool | Shape::Byte | Shape::UShort | Shape::UInt | Shape::Float | Shape::Double, _) => String::from("0"),
+        (Shape::String | Shape::Custom, _) => String::default(),
+        _ => String::from("-1"),
+    }
+}
+
+fn hex_bytes(shape: &Shape, v: &CellValue, model: &TableModel) -> String {
+    let text = scalar(shape, v, model);
+    let bytes = match shape {
+        Shape::Bool | Shape::Byte => vec![text.parse::<u8>().unwrap_or_default()],
+        Shape::SByte => vec![text.parse::<i8>().unwrap_or_default() as u8],
+        Shape::Short => text.parse::<i16>().unwrap_or_default().to_le_bytes().to_vec(),
+        Shape::UShort => text.parse::<u16>().unwrap_or_default().to_le_bytes().to_vec(),
+        Shape::UInt => text.parse::<u32>().unwrap_or_default().to_le_bytes().to_vec(),
+        Shape::Float => match v {
+            CellValue::DFloat(FloatValue(n)) => n.to_le_bytes().to_vec(),
+            _ => 0_f32.to_le_bytes().to_vec(),
+        },
+        Shape::Double => match v {
+            CellValue::DDouble(DoubleValue(n)) => n.to_le_bytes().to_vec(),
+            _ => 0_f64.to_le_bytes().to_vec(),
+        },
+        _ => text.parse::<i32>().unwrap_or_default().to_le_bytes().to_vec(),
+    };
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// s is the text of the value in its own width
+fn float_str(v: f64, s: String) -> String {
+    if v.is_nan() {
+        String::from("NaN")
+    } else if v.is_infinite() {
+        String::from(if v > 0.0 { "Infinity" } else { "-Infinity" })
+    } else {
+        s
+    }
+}
+
+// plain when YAML reads it back as the same string, double quoted otherwise
+fn yaml_str(v: &str) -> String {
+    let plain = !v.is_empty()
+        && !v.starts_with(|c: char| c.is_whitespace() || "-?:,[]{}#&*!|>'\"%@`~".contains(c))
+        && !v.ends_with(|c: char| c.is_whitespace() || c == ':')
+        && !v.contains(": ") && !v.contains(" #")
+        && !v.chars().any(|c| c.is_control())
+        && !["null", "Null", "NULL", "true", "True", "TRUE", "false", "False", "FALSE"].contains(&v);
+    if plain || v.is_empty() {
+        return String::from(v);
+    }
+
+    let mut ret = String::with_capacity(v.len() + 2);
+    ret.push('"');
+    for c in v.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if c.is_control() => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::EnumDef;
+    use std::collections::BTreeSet;
+    use std::rc::Rc;
+
+    fn rc(v: &str) -> Rc<String> {
+        Rc::new(String::from(v))
+    }
+
+    fn list(values: Vec<CellValue>) -> CellValue {
+        CellValue::DList(ListValue(std::iter::once(CellValue::DInt(IntValue(0))).chain(values).collect()))
+    }
+
+    fn model(name: &str, columns: Vec<Column>) -> TableModel {
+        TableModel {
+            name: String::from(name),
+            lines: columns.first().map_or(0, |v| v.values.len()),
+            columns,
+            enums: vec![EnumDef {
+                owner: String::from(name),
+                name: String::from("Kind"),
+                members: vec![(rc("None"), 0, rc("")), (rc("Weapon"), 200, rc(""))],
+                reserved: BTreeSet::default(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn helpers_of(model: &TableModel) -> Vec<Helper> {
+        let mut helpers = Vec::default();
+        for column in model.columns.iter() {
+            unity_type(&shape_of(&column.ty), &column.ty, &format!("{}{}", model.name, column.ident), "Item", &mut helpers);
+        }
+        helpers
+    }
+
+    #[test]
+    fn guids_only_depend_on_the_file_name() {
+        assert_eq!(guid("ItemAsset.cs"), guid("ItemAsset.cs"));
+        assert_eq!(guid("ItemAsset.cs"), "61f26a9674a87e29bb7668fa9d46555a");
+        assert_ne!(guid("ItemAsset.cs"), guid("Item.asset"));
+    }
+
+    #[test]
+    fn enums_and_lists_in_yaml() {
+        let enum_cell = |v: &str| CellValue::DEnum(EnumValue(rc("Kind"), rc(v), rc("UnityYaml")));
+        let pair = |a: i32, b: &str| CellValue::DValueTuple(ValueTupleValue(vec![CellValue::DInt(IntValue(a)), CellValue::DString(StringValue(rc(b)))]));
+        let model = model("UnityYaml", vec![
+            Column::new("Kind", "enum", vec![enum_cell("Weapon"), enum_cell("")]),
+            Column::new("Kinds", "List<enum>", vec![list(vec![enum_cell("None"), enum_cell("Weapon")]), list(vec![])]),
+            Column::new("Ids", "List<short>", vec![list(vec![CellValue::DShort(ShortValue(1)), CellValue::DShort(ShortValue(-2))]), list(vec![])]),
+            Column::new("Pairs", "List<ValueTuple<int,string>>", vec![list(vec![pair(1, "a: b")]), list(vec![])]),
+        ]);
+
+        let lines = asset_lines(&model, "0");
+        let items = lines.iter().skip_while(|v| v.as_str() != "  Items:").skip(1).map(|v| v.as_str()).collect::<Vec<&str>>();
+        assert_eq!(items, vec![
+            "  - Kind: 200",
+            "    Kinds: 00000000c8000000",
+            "    Ids: 0100feff",
+            "    Pairs:",
+            "    - Item1: 1",
+            "      Item2: \"a: b\"",
+            "  - Kind: -1",
+            "    Kinds: []",
+            "    Ids: []",
+            "    Pairs: []",
+        ]);
+        assert_eq!(asset_lines(&TableModel::default(), "0").last().unwrap(), "  Items: []");
+    }
+
+    #[test]
+    fn helper_names_must_be_free() {
+        // the nested list Reward and the tuple RewardItem both need UnityNamesRewardItem
+        let dup = model("UnityNames", vec![
+            Column::new("Reward", "List<List<int>>", vec![]),
+            Column::new("RewardItem", "ValueTuple<int,int>", vec![]),
+        ]);
+        assert!(claim_names(&dup, Some(&helpers_of(&dup))).is_err());
+
+        // the tuple Reward of UnityQuest is the class of the table UnityQuestReward
+        let quest = model("UnityQuest", vec![Column::new("Reward", "ValueTuple<int,int>", vec![])]);
+        assert!(claim_names(&quest, Some(&helpers_of(&quest))).is_ok());
+        assert!(claim_names(&model("UnityQuestReward", vec![]), None).is_err());
+    }
+}
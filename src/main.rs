@@ -6,12 +6,19 @@ use defs::{
     OUTPUT_SCRIPT_CODE_DIR, 
     SOURCE_XLSXS_DIR, 
     DEFAULT_SOURCE_SUFFIX, 
-    DEFAULT_DEST_SUFFIX, REF_TEXT_DIR, OUTPUT_ENUM_CODE_DIR, LINE_END_FLAG
+    DEFAULT_DEST_SUFFIX, LINE_END_FLAG, REF_TEXT_DIR, OUTPUT_ENUM_CODE_DIR, ENUM_LIBRARY_NAME, GEN_ENUM_HELPERS,
+    OUTPUT_DATA_DIR, EXPORT_FORMATS, GEN_MSGPACK_CLASSES, OUTPUT_PROTO_DIR, OUTPUT_LUA_DIR,
+    OUTPUT_TS_DIR, GEN_TS_JSON, OUTPUT_CPP_DIR, OUTPUT_RS_DIR, CS_TEMPLATE_DIR,
+    OUTPUT_DOCS_DIR, DOCS_FORMAT, OUTPUT_GRAPH_DIR, GRAPH_FOCUS, GRAPH_DEPTH,
+    OUTPUT_UNITY_DIR, UNITY_TABLES, PROJECT_CONFIG, CS_COLLECTIONS, POOL_LITERALS, DEFAULT_ENUM_HISTORY_SUFFIX
 };
 
 mod parser;
+mod emitter;
 mod reference;
 mod pull;
+mod dump;
+mod naming;
 
 mod args;
 use args::Args;
@@ -22,7 +29,7 @@ use reference::RefData;
 use xlsx_read::excel_file::ExcelFile;
 use xlsx_read::excel_table::ExcelTable;
 
-use std::collections::{HashSet, BTreeMap};
+use std::collections::{HashSet, HashMap, BTreeMap};
 use std::fs::File;
 use std::io::Write;
 use std::rc::Rc;
@@ -34,10 +41,15 @@ use std::path::{Path, PathBuf};
 use std::process::exit;
 
 use crate::parser::CellValue;
+use crate::emitter::csharp::collections;
 
 type RefDataMap = DashMap<String, Arc<RefData>>;
+// <enum_name, <desc, (ident, val)>>
+type EnumLibMap = DashMap<String, HashMap<String, (String, i32)>>;
+// <enumflags group, (ident, type, table)>
+type EnumFlagMap = DashMap<String, Vec<(String, String, String)>>;
 
-fn process_xlsx_dir<P: AsRef<Path>>(dir: P, tx: Sender<JoinHandle<()>>) -> Result<(), std::io::Error> {
+fn process_xlsx_dir<P: AsRef<Path>>(dir: P, tx: Sender<JoinHandle<Result<(), std::io::Error>>>) -> Result<(), std::io::Error> {
     for entry in fs::read_dir(dir)? {
         let path = entry?.path();
         let base_name = path.file_name().unwrap().to_str().unwrap();
@@ -49,18 +61,21 @@ fn process_xlsx_dir<P: AsRef<Path>>(dir: P, tx: Sender<JoinHandle<()>>) -> Resul
         if path.is_dir() && !path.file_name().is_some_and(|v| { v.to_str().is_some_and(|vv| vv.starts_with('.')) }) {
             //process_xlsx_dir(path, tx.clone())?;
             let tx_clone = tx.clone();
-            let handle = thread::spawn(move || {
-                let _ = process_xlsx_dir(path, tx_clone);
-            });
+            let handle = thread::spawn(move || process_xlsx_dir(path, tx_clone));
             tx.send(handle).unwrap();
         } else if path.extension().is_some_and(|x| x.to_str().unwrap() == DEFAULT_SOURCE_SUFFIX) 
             && !path.file_name().is_some_and(|v| { v.to_str().is_some_and(|vv| vv.starts_with('~')) }) 
         {
             let idx = base_name.find('.').unwrap_or_default();
             
-            if base_name == "GlobalConfig.xlsx" {
-                // sync
-                process_global_config(&path, &base_name[..idx]);
+            if &base_name[..idx] == unsafe { ENUM_LIBRARY_NAME } {
+                // processed before all the tables
+                continue;
+            } else if base_name == "GlobalConfig.xlsx" {
+                // sync, C# only
+                if emitter::enabled("cs") {
+                    process_global_config(&path, &base_name[..idx]);
+                }
             } else {
                 let mut parser = parser::Parser::new();
                 if let Some(refdata) = RefData::new(unsafe { REF_TEXT_DIR }, &base_name[..idx]) {
@@ -73,12 +88,98 @@ fn process_xlsx_dir<P: AsRef<Path>>(dir: P, tx: Sender<JoinHandle<()>>) -> Resul
                     parser.read_file(&base_name[..idx], &path, None)?;
                 }
 
-                let output_path = format!("{}/{}.{}", unsafe { OUTPUT_SCRIPT_CODE_DIR }, &base_name[..idx], DEFAULT_DEST_SUFFIX);
-                let mut file = File::create(output_path)?;
                 println!("Process file_name: {}", base_name);
-                parser.generate("\r\n", &mut file)?;
+                for e in emitter::emitters().iter() {
+                    e.emit(parser.model())?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn process_enum_library() -> Result<(), std::io::Error> {
+    let mut file_name = String::from(unsafe { ENUM_LIBRARY_NAME });
+    file_name.push('.');
+    file_name.push_str(DEFAULT_SOURCE_SUFFIX);
+    let path = parser::find_file(unsafe { SOURCE_XLSXS_DIR }, &file_name);
+
+    if path.is_file() {
+        println!("Process enum library: {}", file_name);
+        let mut parser = parser::Parser::new();
+        parser.read_enum_library(path)?;
+        for e in emitter::emitters().iter() {
+            for def in parser.model().enums.iter() {
+                e.emit_enum(def)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// the enum library, the tables (all of them when name is empty) and finish of every emitter.
+// lstring writes LanguageKey.cs of a full build, pulled first when pull_file
+fn build(name: String, lstring: bool, pull_file: bool) -> Result<(), std::io::Error> {
+    // shared enums must be ready before any table refers to them
+    process_enum_library()?;
+
+    if name.is_empty() {
+        let (tx, rx) = mpsc::channel::<JoinHandle<Result<(), std::io::Error>>>();
+
+        if lstring {
+            let mut ls_path = PathBuf::from(unsafe { SOURCE_XLSXS_DIR });
+            ls_path.push("LString.xlsx");
+            process_lstring_xlsx(ls_path, tx.clone(), pull_file);
+        }
+
+        // the subdirectories fail in their own threads, the build fails with any of them
+        let mut errs = Vec::<std::io::Error>::default();
+        if let Err(e) = process_xlsx_dir(unsafe { SOURCE_XLSXS_DIR }, tx.clone()) {
+            errs.push(e);
+        }
+
+        // !! drop the raw tx
+        drop(tx);
+        while let Ok(handle) = rx.recv() {
+            match handle.join() {
+                Ok(Err(e)) => errs.push(e),
+                Err(_) => errs.push(std::io::Error::new(std::io::ErrorKind::Other, "a table thread panicked")),
+                _ => {}
+            }
+        }
+        if let Some(e) = errs.pop() {
+            for e in errs.iter() {
+                println!("{}", e);
+            }
+            return Err(e);
+        }
+
+        // only a full build of tables that all parsed sees every enumflags column
+        let defs = parser::gen_referenced_types()?;
+        for e in emitter::emitters().iter() {
+            for def in defs.iter() {
+                e.emit_enum(def)?;
             }
         }
+    } else {
+        let mut file_name = String::from(&name);
+        file_name.push('.');
+        file_name.push_str(DEFAULT_SOURCE_SUFFIX);
+        let xlsx_path = parser::find_file(unsafe { SOURCE_XLSXS_DIR }, &file_name);
+
+        let mut parser = parser::Parser::new();
+        match RefData::new(unsafe { REF_TEXT_DIR }, &name) {
+            Some(refdata) => parser.read_file(&name, xlsx_path, Some(Arc::from(refdata)))?,
+            None => parser.read_file(&name, xlsx_path, None)?,
+        }
+
+        for e in emitter::emitters().iter() {
+            e.emit(parser.model())?;
+        }
+    }
+
+    for e in emitter::emitters().iter() {
+        e.finish()?;
     }
     Ok(())
 }
@@ -91,29 +192,20 @@ fn process_global_config<P: AsRef<Path>>(path: P, name: &str) {
             Ok(ret) => {
                 let output_path = format!("{}/{}.{}", unsafe { OUTPUT_SCRIPT_CODE_DIR }, "GlobalConfig", DEFAULT_DEST_SUFFIX);
                 if let Ok(mut file) = File::create(output_path) {
-                    writeln!(file, "////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////");
-                    writeln!(file, "// This File is generated by the program, DO NOT EDIT MANUALLY!");
-                    writeln!(file, "// 此文件由程序生成, 切勿手动编辑!");
-                    writeln!(file, "////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////");
-                    writeln!(file, "using Config;");
-                    writeln!(file, "using System.Collections.Generic;");
-                    writeln!(file, "using Config.Common;");
-                    writeln!(file, "");
-                    writeln!(file, "#pragma warning disable 1591");
-                    writeln!(file, "");
-                    writeln!(file, "[System.Serializable]");
-                    writeln!(file, "public class {} : IConfigData", name);
-                    writeln!(file, "{{");
-                    writeln!(file, "\tpublic int GetItemId(string refName) => throw new System.NotImplementedException();");
-                    writeln!(file, "\tpublic int AddExtraItem(string identifier, string refName, object configItem) => throw new System.NotImplementedException();");
-                    writeln!(file, "\tpublic static {} Instance = new {}();", name, name);
-                    writeln!(file, "");
-
-                    
+                    let naming = naming::of(name);
+                    let mut usings = vec![format!("using {};", naming.namespace), String::from("using System.Collections.Generic;")];
+                    for using in naming.project_usings() {
+                        usings.push(format!("using {};", using));
+                    }
+                    for using in collections::usings() {
+                        usings.push(format!("using {};", using));
+                    }
+
+                    let mut fields = Vec::<u8>::default();
+                    let mut inits = Vec::<u8>::default();
                     for (_, id) in ret.into_iter() {
                         if let Ok(table) = ff.parse_sheet(*id) {
                             let height = table.height();
-                            let mut vals = Vec::<(Rc<String>, CellValue)>::with_capacity(height);
 
                             for row in 1..height {
                                 if let (Some(ident), Some(ty), Some(val), Some(desc), Some(modify)) = (
@@ -123,41 +215,43 @@ fn process_global_config<P: AsRef<Path>>(path: P, name: &str) {
                                     table.cell(3, row),
                                     table.cell(4, row)
                                 ) {
-                                    writeln!(file, "\t/// <summary>");
-                                    writeln!(file, "\t/// {}", desc);
-                                    writeln!(file, "\t/// </summary>");
+                                    writeln!(fields, "\t/// <summary>");
+                                    writeln!(fields, "\t/// {}", desc);
+                                    writeln!(fields, "\t/// </summary>");
                                     
                                     let cell = CellValue::new(val, ty, &Rc::default(), &BTreeMap::default(), ident, &Rc::default(), name, 0, 0);
                                     if modify.as_str() == "0" {
-                                        write!(file, "\tpublic {} {} = ", ty, ident);
-                                        cell.gen_code(&mut file);
-                                        writeln!(file, ";");
+                                        write!(fields, "\tpublic {} {} = ", collections::field_type(ty), ident);
                                     } else if cell.is_arr_or_list() {
-                                        write!(file, "\tpublic static readonly {} {} = ", ty, ident);
-                                        cell.gen_code(&mut file);
-                                        writeln!(file, ";");
+                                        write!(fields, "\tpublic static readonly {} {} = ", collections::field_type(ty), ident);
                                     } else {
-                                        write!(file, "\tpublic const {} {} = ", ty, ident);
-                                        cell.gen_code(&mut file);
-                                        writeln!(file, ";");
+                                        write!(fields, "\tpublic const {} {} = ", ty, ident);
                                     }
+                                    cell.gen_code(&mut fields);
+                                    writeln!(fields, ";");
 
-                                    vals.push((ident.clone(), cell));
+                                    write!(inits, "\t\t{} = ", ident);
+                                    cell.gen_code(&mut inits);
+                                    writeln!(inits, ";");
                                 }
                             }
-
-                            writeln!(file, "");
-                            writeln!(file, "\tpublic void Init()");
-                            writeln!(file, "\t{{");
-                            for (ident, cell) in vals.iter() {
-                                write!(file, "\t\t{} = ", ident);
-                                cell.gen_code(&mut file);
-                                writeln!(file, ";");
-                            }
-                            writeln!(file, "\t}}");
-                            write!(file, "}}");
                         }
                     }
+                    // the line of the placeholder ends the last one
+                    fields.pop();
+                    inits.pop();
+
+                    let text = emitter::csharp::template::render("global_config.tpl", &[
+                        ("Name", name),
+                        ("Usings", &usings.join("\n")),
+                        ("Fields", &String::from_utf8_lossy(&fields)),
+                        ("Inits", &String::from_utf8_lossy(&inits)),
+                    ]);
+                    // GlobalConfig.cs keeps its \n line ends
+                    match text {
+                        Ok(text) => { file.write(text.replace(LINE_END_FLAG, "\n").as_bytes()); },
+                        Err(e) => println!("{}", e),
+                    }
                 }
             }
             _ => {}
@@ -166,11 +260,11 @@ fn process_global_config<P: AsRef<Path>>(path: P, name: &str) {
 }
 
 #[allow(unused_must_use)]
-fn process_lstring_xlsx<P: AsRef<Path> + std::marker::Send + 'static>(path: P, sx: Sender<JoinHandle<()>>, is_need: bool) {
+fn process_lstring_xlsx<P: AsRef<Path> + std::marker::Send + 'static>(path: P, sx: Sender<JoinHandle<Result<(), std::io::Error>>>, is_need: bool) {
     let handle = thread::spawn(move || {
         if is_need && !pull_file() {
             println!("pull file failed");
-            return
+            return Ok(())
         }
         let file = ExcelFile::load_from_path(path);
         let mut tables = Vec::<ExcelTable>::default();
@@ -236,12 +330,15 @@ fn process_lstring_xlsx<P: AsRef<Path> + std::marker::Send + 'static>(path: P, s
                 _ => {}
             }
         }
+        Ok(())
     });
     sx.send(handle).unwrap();
 }
 
 lazy_static! (
     static ref RDM: RefDataMap = DashMap::default();
+    static ref ENUM_LIB: EnumLibMap = DashMap::default();
+    static ref ENUM_FLAGS: EnumFlagMap = DashMap::default();
     static ref FILE_NAME_FILTER: HashSet<&'static str> = {
         let mut ret = HashSet::<&'static str>::default();
         ret.insert("NameCore_CN.xlsx");
@@ -253,6 +350,19 @@ lazy_static! (
     };
 );
 
+// the enum histories stay, values retired by them may never be reused
+fn clean_ref_dir(dir: &str) -> Result<(), std::io::Error> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else if !path.to_string_lossy().ends_with(DEFAULT_ENUM_HISTORY_SUFFIX) {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
 fn main() {
     let args = Args::parse();
 
@@ -261,10 +371,34 @@ fn main() {
         OUTPUT_ENUM_CODE_DIR = Box::leak(args.output_enum_dir.into_boxed_str());
         SOURCE_XLSXS_DIR = Box::leak(args.src_table_dir.into_boxed_str());
         REF_TEXT_DIR = Box::leak(args.ref_mapping_dir.into_boxed_str());
+        ENUM_LIBRARY_NAME = Box::leak(args.enum_library.into_boxed_str());
+        GEN_ENUM_HELPERS = args.enum_helpers;
+        GEN_MSGPACK_CLASSES = args.msgpack_classes;
+        GEN_TS_JSON = args.ts_json;
+        OUTPUT_DATA_DIR = Box::leak(args.output_data_dir.into_boxed_str());
+        OUTPUT_PROTO_DIR = Box::leak(args.output_proto_dir.into_boxed_str());
+        OUTPUT_LUA_DIR = Box::leak(args.output_lua_dir.into_boxed_str());
+        OUTPUT_TS_DIR = Box::leak(args.output_ts_dir.into_boxed_str());
+        OUTPUT_CPP_DIR = Box::leak(args.output_cpp_dir.into_boxed_str());
+        OUTPUT_RS_DIR = Box::leak(args.output_rs_dir.into_boxed_str());
+        OUTPUT_DOCS_DIR = Box::leak(args.output_docs_dir.into_boxed_str());
+        OUTPUT_GRAPH_DIR = Box::leak(args.output_graph_dir.into_boxed_str());
+        OUTPUT_UNITY_DIR = Box::leak(args.output_unity_dir.into_boxed_str());
+        EXPORT_FORMATS = Box::leak(args.export.into_boxed_str());
+        CS_TEMPLATE_DIR = Box::leak(args.template_dir.into_boxed_str());
+        UNITY_TABLES = Box::leak(args.unity_tables.into_boxed_str());
+        PROJECT_CONFIG = Box::leak(args.project_config.into_boxed_str());
+        CS_COLLECTIONS = Box::leak(args.cs_collections.into_boxed_str());
+        POOL_LITERALS = args.pool_literals;
     }
 
     match args.command {
         args::Command::Build => {
+            if let Err(e) = emitter::check_formats().and_then(|_| naming::check()).and_then(|_| emitter::csharp::collections::check()) {
+                println!("{}", e);
+                exit(-1);
+            }
+
             if let Err(_) = fs::metadata(unsafe { OUTPUT_SCRIPT_CODE_DIR }) {
                 if let Err(_) = fs::create_dir_all(unsafe { OUTPUT_SCRIPT_CODE_DIR }) {
                     exit(-1)
@@ -288,65 +422,80 @@ fn main() {
                 update_svn();
             }
 
-            if args.name.is_empty() {
-                let (tx, rx) = mpsc::channel::<JoinHandle<()>>();
-
-                let mut ls_path = PathBuf::from(unsafe { SOURCE_XLSXS_DIR });
-                ls_path.push("LString.xlsx");
-                process_lstring_xlsx(ls_path, tx.clone(), args.pull_file);
-
-                if let Err(e) = process_xlsx_dir(unsafe { SOURCE_XLSXS_DIR }, tx.clone()) {
-                    println!("{}", e);
-                    exit(-1);
-                }
-
-                // !! drop the raw tx
-                drop(tx);
-                while let Ok(handle) = rx.recv() {
-                    let _ = handle.join();
-                }
-            } else {
-                let base_name = args.name;
-                let mut file_name = String::from(&base_name);
-                file_name.push('.');
-                file_name.push_str(DEFAULT_SOURCE_SUFFIX);
-                let xlsx_path = parser::find_file(unsafe { SOURCE_XLSXS_DIR }, &file_name);
-
-                let mut parser = parser::Parser::new();
-
-                if let Some(refdata) = RefData::new(unsafe { REF_TEXT_DIR }, &base_name) {
-                    if let Err(e) = parser.read_file(&base_name, xlsx_path, Some(Arc::from(refdata))) {
-                        println!("{}", e);
-                        exit(-1);
-                    }
-                } else {
-                    if let Err(e) = parser.read_file(&base_name, xlsx_path, None) {
-                        println!("{}", e);
-                        exit(-1);
-                    }
-                }
-
-                let output_path = format!("{}/{}.{}", unsafe { OUTPUT_SCRIPT_CODE_DIR }, base_name, DEFAULT_DEST_SUFFIX);
-                if let Ok(mut file) = File::create(output_path) {
-                    if let Err(e) = parser.generate(LINE_END_FLAG, &mut file) {
+            if let Err(e) = build(args.name, true, args.pull_file) {
+                println!("{}", e);
+                exit(-1)
+            }
+        },
+        args::Command::Clean => {
+            if let Err(e) = fs::remove_dir_all(unsafe { OUTPUT_SCRIPT_CODE_DIR }) {
+                println!("{}", e);
+                exit(-1)
+            }
+            if let Err(e) = fs::remove_dir_all(unsafe { OUTPUT_ENUM_CODE_DIR }) {
+                println!("{}", e);
+                exit(-1)
+            }
+            if let Err(e) = clean_ref_dir(unsafe { REF_TEXT_DIR }) {
+                println!("{}", e);
+                exit(-1)
+            }
+            // only there when a data format was exported
+            for dir in [unsafe { OUTPUT_DATA_DIR }, unsafe { OUTPUT_PROTO_DIR }, unsafe { OUTPUT_LUA_DIR }, unsafe { OUTPUT_TS_DIR }, unsafe { OUTPUT_CPP_DIR }, unsafe { OUTPUT_RS_DIR }, unsafe { OUTPUT_DOCS_DIR }, unsafe { OUTPUT_GRAPH_DIR }, unsafe { OUTPUT_UNITY_DIR }] {
+                if let Ok(_) = fs::metadata(dir) {
+                    if let Err(e) = fs::remove_dir_all(dir) {
                         println!("{}", e);
                         exit(-1)
                     }
-                } else {
-                    exit(-1)
                 }
             }
         },
-        args::Command::Clean => {
-            if let Err(e) = fs::remove_dir_all(unsafe { OUTPUT_SCRIPT_CODE_DIR }) {
+        args::Command::Dump { format } => {
+            let ret = if args.name.is_empty() {
+                dump::dump_dir(unsafe { SOURCE_XLSXS_DIR }, &format)
+            } else {
+                dump::dump_name(unsafe { SOURCE_XLSXS_DIR }, &args.name, &format)
+            };
+            if let Err(e) = ret {
                 println!("{}", e);
                 exit(-1)
             }
-            if let Err(e) = fs::remove_dir_all(unsafe { OUTPUT_ENUM_CODE_DIR }) {
+        },
+        args::Command::Docs { format } => {
+            unsafe {
+                DOCS_FORMAT = Box::leak(format.into_boxed_str());
+                EXPORT_FORMATS = "docs";
+            }
+            if let Err(e) = emitter::docs::check_format().and_then(|_| naming::check()) {
                 println!("{}", e);
                 exit(-1)
             }
-            if let Err(e) = fs::remove_dir_all(unsafe { REF_TEXT_DIR }) {
+            if let Err(e) = build(args.name, false, false) {
+                println!("{}", e);
+                exit(-1)
+            }
+        },
+        args::Command::Graph { focus, depth } => {
+            unsafe {
+                GRAPH_FOCUS = Box::leak(focus.into_boxed_str());
+                GRAPH_DEPTH = depth;
+                EXPORT_FORMATS = "graph";
+            }
+            if let Err(e) = naming::check() {
+                println!("{}", e);
+                exit(-1)
+            }
+            if let Err(e) = build(args.name, false, false) {
+                println!("{}", e);
+                exit(-1)
+            }
+        },
+        args::Command::Templates => {
+            if unsafe { CS_TEMPLATE_DIR }.is_empty() {
+                println!("--template-dir is required");
+                exit(-1)
+            }
+            if let Err(e) = emitter::csharp::template::write_defaults(unsafe { CS_TEMPLATE_DIR }) {
                 println!("{}", e);
                 exit(-1)
             }
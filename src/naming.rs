@@ -0,0 +1,187 @@
+use crate::defs::PROJECT_CONFIG;
+use crate::parser::TableModel;
+use lazy_static::lazy_static;
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+
+// names of the generated C#, set by the project config and overridden per workbook:
+// {
+//     "namespace": "Game.Data.Config",
+//     "usings": ["System", "System.Collections.Generic", "Game.Data.Config.Common"],
+//     "item_suffix": "Row",
+//     "class_suffix": "Table",
+//     "enum_name": "E{Base}{Name}",
+//     "workbooks": { "Item": { "namespace": "Game.Data.Config.Items" } }
+// }
+#[derive(Clone, Debug)]
+pub struct Naming {
+    pub namespace: String,
+    pub usings: Vec<String>,
+    pub item_suffix: String,
+    pub class_suffix: String,
+    pub enum_name: String,
+}
+
+const DEFAULT_NAMESPACE: &'static str = "Config";
+
+impl Default for Naming {
+    fn default() -> Self {
+        Naming {
+            namespace: String::from(DEFAULT_NAMESPACE),
+            usings: ["System", "System.Linq", "System.Collections", "System.Collections.Generic", "Config.Common"]
+                .iter().map(|v| String::from(*v)).collect(),
+            item_suffix: String::from("Item"),
+            class_suffix: String::default(),
+            enum_name: String::from("E{Base}{Name}"),
+        }
+    }
+}
+
+impl Naming {
+    // {Name}Item
+    pub fn item_class(&self, name: &str) -> String {
+        format!("{}{}", name, self.item_suffix)
+    }
+
+    // {Name}, the table class with Instance
+    pub fn table_class(&self, name: &str) -> String {
+        format!("{}{}", name, self.class_suffix)
+    }
+
+    // the enums are global unless a namespace other than the default one is configured
+    pub fn enum_namespace(&self) -> Option<&str> {
+        if self.namespace == DEFAULT_NAMESPACE { None } else { Some(&self.namespace) }
+    }
+
+    // the usings which are not System ones, for the files with usings of their own
+    pub fn project_usings(&self) -> Vec<&String> {
+        self.usings.iter().filter(|v| *v != "System" && !v.starts_with("System.")).collect()
+    }
+
+    fn merge(&self, obj: &Map<String, Value>, at: &str) -> std::result::Result<Naming, String> {
+        let mut ret = self.clone();
+        for (k, v) in obj.iter() {
+            let text = || v.as_str().map(String::from).ok_or(format!("{}.{} should be a string", at, k));
+            match k.as_str() {
+                "namespace" => ret.namespace = check_namespace(&text()?, &format!("{}.{}", at, k))?,
+                "item_suffix" => ret.item_suffix = check_ident(&text()?, &format!("{}.{}", at, k), true)?,
+                "class_suffix" => ret.class_suffix = check_ident(&text()?, &format!("{}.{}", at, k), true)?,
+                "enum_name" => {
+                    let pattern = text()?;
+                    if !pattern.contains("{Name}") {
+                        return Err(format!("{}.enum_name {} has no {{Name}}", at, pattern));
+                    }
+                    check_ident(&pattern.replace("{Base}", "").replace("{Name}", "X"), &format!("{}.enum_name", at), false)?;
+                    ret.enum_name = pattern;
+                }
+                "usings" => {
+                    let arr = v.as_array().ok_or(format!("{}.usings should be an array of strings", at))?;
+                    ret.usings.clear();
+                    for (i, u) in arr.iter().enumerate() {
+                        let u = u.as_str().ok_or(format!("{}.usings[{}] should be a string", at, i))?;
+                        ret.usings.push(check_namespace(u, &format!("{}.usings[{}]", at, i))?);
+                    }
+                }
+                "workbooks" if at == "project" => {}
+                _ => return Err(format!("unknown key {}.{}, expected namespace, usings, item_suffix, class_suffix, enum_name{}",
+                    at, k, if at == "project" { " or workbooks" } else { "" })),
+            }
+        }
+        if ret.item_suffix == ret.class_suffix {
+            return Err(format!("{}: item_suffix and class_suffix are both \"{}\", the classes would collide", at, ret.item_suffix));
+        }
+        Ok(ret)
+    }
+}
+
+struct Project {
+    base: Naming,
+    workbooks: HashMap<String, Naming>,
+}
+
+lazy_static! (
+    static ref PROJECT: std::result::Result<Project, String> = load();
+);
+
+// reports the errors of the project config before anything is generated
+pub fn check() -> Result<()> {
+    match PROJECT.as_ref() {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Error::new(ErrorKind::InvalidInput, format!("project config {}: {}", unsafe { PROJECT_CONFIG }, e))),
+    }
+}
+
+// names of a workbook, the project ones without an override. "" for the shared enums
+pub fn of(workbook: &str) -> &'static Naming {
+    lazy_static! (
+        static ref DEFAULT: Naming = Naming::default();
+    );
+    match PROJECT.as_ref() {
+        Ok(v) => v.workbooks.get(workbook).unwrap_or(&v.base),
+        Err(_) => &DEFAULT,
+    }
+}
+
+// E{Base}{Name} by the pattern of the owner, the type every emitter gives the enum
+pub fn enum_type(owner: &str, name: &str) -> String {
+    of(owner).enum_name.replace("{Base}", owner).replace("{Name}", name)
+}
+
+// namespaces of the enums of other workbooks and the shared ones which the classes of the workbook use
+pub fn enum_usings(model: &TableModel) -> Vec<String> {
+    let own = of(&model.name).namespace.as_str();
+    let mut owners = model.enum_refs.iter().map(|v| v.owner.as_str()).collect::<BTreeSet<&str>>();
+    if !model.enumflags.is_empty() {
+        owners.insert("");
+    }
+    owners.iter().filter_map(|v| of(v).enum_namespace())
+        .filter(|v| *v != own)
+        .map(String::from)
+        .collect::<BTreeSet<String>>()
+        .into_iter().collect()
+}
+
+// the namespace every generated class lives in, the loaders and GlobalConfig use it
+pub fn project() -> &'static Naming {
+    of("")
+}
+
+fn load() -> std::result::Result<Project, String> {
+    let path = unsafe { PROJECT_CONFIG };
+    if path.is_empty() {
+        return Ok(Project { base: Naming::default(), workbooks: HashMap::default() });
+    }
+
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let root = serde_json::from_str::<Value>(&text).map_err(|e| e.to_string())?;
+    let root = root.as_object().ok_or(String::from("should be an object"))?;
+    let base = Naming::default().merge(root, "project")?;
+
+    let mut workbooks = HashMap::default();
+    if let Some(v) = root.get("workbooks") {
+        let v = v.as_object().ok_or(String::from("workbooks should be an object of workbook names"))?;
+        for (name, obj) in v.iter() {
+            let obj = obj.as_object().ok_or(format!("workbooks.{} should be an object", name))?;
+            workbooks.insert(name.clone(), base.merge(obj, &format!("workbooks.{}", name))?);
+        }
+    }
+    Ok(Project { base, workbooks })
+}
+
+fn check_ident(v: &str, at: &str, allow_empty: bool) -> std::result::Result<String, String> {
+    let valid = if v.is_empty() {
+        allow_empty
+    } else {
+        !v.starts_with(|c: char| c.is_ascii_digit()) && v.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    if valid { Ok(String::from(v)) } else { Err(format!("{} \"{}\" is not a C# identifier", at, v)) }
+}
+
+fn check_namespace(v: &str, at: &str) -> std::result::Result<String, String> {
+    for part in v.split('.') {
+        check_ident(part, at, false).map_err(|_| format!("{} \"{}\" is not a C# namespace", at, v))?;
+    }
+    Ok(String::from(v))
+}
@@ -1,59 +1,42 @@
-use crate::{defs::*, reference::RefData};
+use crate::{defs::*, naming, reference::RefData, ENUM_LIB, ENUM_FLAGS};
 use std::{
     cell::RefCell,
-    collections::{HashMap, HashSet, BTreeMap},
+    collections::{HashMap, HashSet, BTreeMap, BTreeSet},
     io::{Error, ErrorKind, Result, Write},
     rc::Rc, path::Path, fs::{File, OpenOptions}, sync::Arc
 };
 use std::path::PathBuf;
 use std::fs;
 use lazy_static::lazy_static;
+use dashmap::DashMap;
 use xlsx_read::{excel_file::ExcelFile, excel_table::ExcelTable};
 
-use item_class::ItemClass;
-mod item_class;
-
-use base_class::BaseClass;
-mod base_class;
-
 pub use cell_value::CellValue;
 pub mod cell_value;
 
+pub use model::{TableModel, Column, EnumDef};
+mod model;
+
 use self::fk_value::{FKValue, RawValData};
 mod fk_value;
 
 mod stack;
-mod bm_search;
+pub(crate) mod bm_search;
 mod fsm;
 
 type LSMap = Rc<RefCell<HashMap<Rc<String>, i32>>>;
 type LSEmptyMap = BTreeMap<(usize, usize), Vec<i32>>;
 type ENMap = Rc<RefCell<HashMap<ItemStr, ItemStr>>>;
-
-trait CodeGenerator {
-    fn gen_code<W: Write + ?Sized>(&self, end: &'static str, tab_nums: i32, stream: &mut W) -> Result<()>;
-}
+pub type EnumMember = (Rc<String>, i32, Rc<String>); // (ident, val, desc)
 
 pub enum KeyType {
     None,
     DefKey(Vec<(ItemStr, usize, ItemStr)>),
 }
 
-pub struct DefaultData(HashMap<Rc<String>, Box<CellValue>>);
-impl Default for DefaultData {
-    fn default() -> Self {
-        DefaultData(HashMap::with_capacity(20))
-    }
-}
-
-pub struct VarData(HashMap<Rc<String>, Vec<Box<CellValue>>>);
-impl Default for VarData {
-    fn default() -> Self {
-        VarData(HashMap::with_capacity(20))
-    }
-}
-
 lazy_static! (
+    // enum type -> workbook, the enum_name of a project without {Base} gives t_Kind of two workbooks the same type
+    static ref ENUM_TYPES: DashMap<String, String> = DashMap::default();
     static ref ENUM_FLAGS_FILTER: HashSet<&'static str> = {
         let mut ret = HashSet::<&'static str>::default();
         ret.insert("Inherit");
@@ -70,92 +53,23 @@ lazy_static! (
 );
 
 pub struct Parser {
-    item_class: ItemClass,
-    base_class: BaseClass,
-    defaults: Rc<RefCell<DefaultData>>,
-    vals: Rc<RefCell<VarData>>,
-    required_fields: Rc<RefCell<Vec<ItemStr>>>,
-    key_type: Rc<RefCell<KeyType>>,
+    model: TableModel,
     skip_cols: Vec<usize>,
-    enmap: Rc<RefCell<HashMap<String, ENMap>>>,
-    nodefs: Rc<RefCell<HashSet<Rc<String>>>>,
-    enumflags: Rc<RefCell<HashMap<String, Vec<Rc<String>>>>>
-}
-
-impl CodeGenerator for Parser {
-    fn gen_code<W: Write + ?Sized>(&self, end: &'static str, tab_nums: i32, stream: &mut W) -> Result<()> {
-        // comment
-        stream.write("////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write("// This File is generated by the program, DO NOT EDIT MANUALLY!".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write("// 此文件由程序生成, 切勿手动编辑!".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write("////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////".as_bytes())?;
-        stream.write(end.as_bytes())?;
-
-        // using
-        stream.write("using System;".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write("using System.Linq;".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write("using System.Collections;".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write("using System.Collections.Generic;".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write("using Config.Common;".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write(end.as_bytes())?;
-
-        // #pragma
-        stream.write("#pragma warning disable 1591".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write(end.as_bytes())?;
-
-        // namespace-start
-        stream.write("namespace Config".as_bytes())?;
-        stream.write(end.as_bytes())?;
-        stream.write("{".as_bytes())?;
-        stream.write(end.as_bytes())?;
-
-        // ItemClass
-        self.item_class.gen_code(end, tab_nums + 1, stream)?;
-        stream.write(end.as_bytes())?;
-        // empty line
-        stream.write(end.as_bytes())?;
-        // BaseClass
-        self.base_class.gen_code(end, tab_nums + 1, stream)?;
-        stream.write(end.as_bytes())?;
-
-        // namespace-end
-        stream.write("}".as_bytes())?;
-
-        Ok(())
-    }
+    enmap: Rc<RefCell<HashMap<String, ENMap>>>
 }
 
 impl Parser {
     pub fn new() -> Self {
         Parser {
-            item_class: ItemClass::default(),
-            base_class: BaseClass::default(),
-            defaults: Rc::from(RefCell::from(DefaultData::default())),
-            vals: Rc::from(RefCell::from(VarData::default())),
-            key_type: Rc::from(RefCell::from(KeyType::None)),
+            model: TableModel::default(),
             skip_cols: Vec::default(),
-            required_fields: Rc::from(RefCell::from(Vec::default())),
-            enmap: Rc::from(RefCell::from(HashMap::<String, ENMap>::default())),
-            nodefs: Rc::default(),
-            enumflags: Rc::default()
+            enmap: Rc::from(RefCell::from(HashMap::<String, ENMap>::default()))
         }
     }
 
     pub fn read_file<P: AsRef<Path>>(&mut self, base_name: &str, path: P, refdata: Option<Arc<RefData>>) -> Result<()> {
-        self.item_class.name = String::from(base_name);
-        self.base_class.name = String::from(base_name);
-        if refdata.is_some() {
-            self.base_class.refdata = Some(refdata.as_ref().unwrap().clone());
-        }
+        self.model.name = String::from(base_name);
+        self.model.refdata = refdata.clone();
         
         let file = ExcelFile::load_from_path(path);
         if let Ok(mut ff) = file {
@@ -163,15 +77,18 @@ impl Parser {
                 Ok(ret) => {
                     let mut template_table = Option::<ExcelTable>::None;
                     for (name, id) in ret.into_iter() {
-                        if let Ok(table) = ff.parse_sheet(*id) {
+                        // a sheet of the table that can't be read would drop its columns and members silently
+                        if name == "Template" || name.starts_with("t_") {
+                            let table = ff.parse_sheet(*id).map_err(|e| Error::new(ErrorKind::Other, format!("{}.xlsx -> {}: {}", base_name, name, e)))?;
                             match name.as_str() {
                                 "Template" => { template_table = Some(table); },
-                                v if v.starts_with("t_") => { self.parse_enum(table, &name[2..], base_name)?; }
-                                _ => {}
+                                _ => { self.parse_enum(table, &name[2..], base_name)?; }
                             }
                         }
                     }
-                    template_table.map(|table| self.parse_template(table, base_name, refdata.clone()));
+                    if let Some(table) = template_table {
+                        self.parse_template(table, base_name, refdata.clone())?;
+                    }
                 },
                 Err(e) => {
                     return Err(Error::new(ErrorKind::Other, e));
@@ -186,8 +103,39 @@ impl Parser {
         Ok(())
     }
 
-    pub fn generate<W: Write + ?Sized>(&self, end: &'static str, stream: &mut W) -> Result<()> {
-        self.gen_code(end, 0, stream)
+    pub fn read_enum_library<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let file = ExcelFile::load_from_path(path);
+        if let Ok(mut ff) = file {
+            match ff.parse_workbook() {
+                Ok(ret) => {
+                    for (name, id) in ret.into_iter() {
+                        if let Ok(table) = ff.parse_sheet(*id) {
+                            if name.starts_with("t_") {
+                                // project-wide enums have no base name prefix
+                                self.parse_enum(table, &name[2..], "")?;
+                            }
+                        }
+                    }
+                },
+                Err(e) => {
+                    return Err(Error::new(ErrorKind::Other, e));
+                }
+            }
+        } else if let Err(e) = file {
+            return Err(Error::new(ErrorKind::Other, e));
+        }
+
+        for def in self.model.enums.iter() {
+            let members = def.members.iter().map(|(ident, val, desc)| {
+                (desc.to_string(), (ident.to_string(), *val))
+            }).collect();
+            ENUM_LIB.insert(def.name.clone(), members);
+        }
+        Ok(())
+    }
+
+    pub fn model(&self) -> &TableModel {
+        &self.model
     }
 
     pub(crate) fn get_table_with_id<P: AsRef<Path>>(path: P, sheet: &str) -> Result<ExcelTable> {
@@ -218,44 +166,100 @@ impl Parser {
     //------------------------private---------------------------------
 
     fn parse_enum(&mut self, table: ExcelTable, enum_name: &str, base_name: &str) -> Result<()> {
-        let height = table.height();
         let en_map = ENMap::default();
         
-        let dest = format!("{}/E{}{}.cs", unsafe { OUTPUT_ENUM_CODE_DIR }, base_name, enum_name);
-        if let Ok(mut file) = File::create(dest) {
-            file.write("#pragma warning disable 1591".as_bytes())?;
-            file.write(LINE_END_FLAG.as_bytes())?;
-            file.write(LINE_END_FLAG.as_bytes())?;
-            file.write("/// <summary>".as_bytes())?;
-            file.write(LINE_END_FLAG.as_bytes())?;
-            file.write_fmt(format_args!("/// {} -> {}{}", base_name, enum_name, LINE_END_FLAG))?;
-            file.write("/// </summary>".as_bytes())?;
-            file.write(LINE_END_FLAG.as_bytes())?;
-            file.write_fmt(format_args!("public enum E{}{}{}", base_name, enum_name, LINE_END_FLAG))?;
-            file.write("{".as_bytes())?;
-            file.write(LINE_END_FLAG.as_bytes())?;
-
-            for row in 0..height {
-                if let (Some(ident), Some(val), Some(desc)) = 
-                    (table.cell(ENUM_COL_IDENT, row), table.cell(ENUM_COL_VAL, row), table.cell(ENUM_COL_DESC, row)) {
-                    file.write_fmt(format_args!("{}/// <summary>{}", '\t', LINE_END_FLAG))?;
-                    file.write_fmt(format_args!("{}/// {}{}", '\t', desc, LINE_END_FLAG))?;
-                    file.write_fmt(format_args!("{}/// </summary>{}", '\t', LINE_END_FLAG))?;
-                    file.write_fmt(format_args!("{}{} = {},{}", '\t', ident, val, LINE_END_FLAG))?;
-                    en_map.as_ref().borrow_mut().insert(Some(desc.clone()), Some(ident.clone()));
-                }
+        // enum:X would silently resolve to the library one
+        if !base_name.is_empty() && ENUM_LIB.contains_key(enum_name) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("duplicate enum: {}.xlsx -> t_{} is already defined in {}.xlsx",
+                base_name, enum_name, unsafe { ENUM_LIBRARY_NAME })));
+        }
+
+        let type_name = naming::enum_type(base_name, enum_name);
+        let owner = if base_name.is_empty() { unsafe { ENUM_LIBRARY_NAME } } else { base_name };
+        if let Some(other) = ENUM_TYPES.insert(type_name.clone(), String::from(owner)).filter(|v| v != owner) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("duplicate enum: {}.xlsx -> t_{} and {}.xlsx are both {}, add {{Base}} to enum_name",
+                owner, enum_name, other, type_name)));
+        }
+
+        // the history of the previous build is the record of the values already in use
+        let (members, mut reserved, mut errs) = Self::read_enum_members(&table);
+        check_prev_enum(read_prev_enum(&type_name), &members, &mut reserved, &mut errs);
+        if !errs.is_empty() {
+            for e in errs.iter() {
+                println!("{}: {}", type_name, e);
             }
+            return Err(Error::new(ErrorKind::InvalidData, format!("invalid enum: {}", type_name)));
+        }
+        write_enum_history(&type_name, &members, &reserved)?;
 
-            file.write_fmt(format_args!("{}Count{}", '\t', LINE_END_FLAG))?;
-            file.write("}".as_bytes())?;
-            file.flush()?;
-            self.enmap.as_ref().borrow_mut().insert(String::from(enum_name), en_map);
+        for (ident, _, desc) in members.iter() {
+            en_map.as_ref().borrow_mut().insert(Some(desc.clone()), Some(ident.clone()));
         }
+        self.enmap.as_ref().borrow_mut().insert(String::from(enum_name), en_map);
+        self.model.enums.push(EnumDef { owner: String::from(base_name), name: String::from(enum_name), members, reserved });
 
         Ok(())
     }
 
-    fn parse_template(&mut self, table: ExcelTable, base_name: &str, refdata: Option<Arc<RefData>>) {
+    fn read_enum_members(table: &ExcelTable) -> (Vec<EnumMember>, BTreeSet<i32>, Vec<String>) {
+        enum_members((0..table.height()).map(|row| (table.cell(ENUM_COL_IDENT, row), table.cell(ENUM_COL_VAL, row), table.cell(ENUM_COL_DESC, row))))
+    }
+
+    // load enums referenced as enum:Base.Name from the t_ sheets of other workbooks
+    fn load_enum_refs(&mut self, table: &ExcelTable, base_name: &str) {
+        for col in 0..table.width() {
+            if let Some(ty) = table.cell(col, DATA_TYPE_ROW) {
+                // every enum:.. of the type, ValueTuple<enum:A,enum:B> has two
+                for (idx, _) in ty.match_indices("enum:") {
+                    let inner = ty[idx..].split(|c| c == '>' || c == ']' || c == '[' || c == ',').next().unwrap_or_default().trim();
+                    self.load_enum_ref(inner, base_name);
+                }
+            }
+        }
+    }
+
+    fn load_enum_ref(&mut self, inner: &str, base_name: &str) {
+        let (key, owner, name) = enum_ref(inner, base_name);
+        if owner == base_name || self.enmap.as_ref().borrow().contains_key(&key) {
+            return;
+        }
+
+        if owner.is_empty() {
+            if let Some(members) = ENUM_LIB.get(name) {
+                let en_map = ENMap::default();
+                let mut def = EnumDef { owner: String::default(), name: String::from(name), members: Vec::default(), reserved: BTreeSet::default() };
+                for (desc, (ident, val)) in members.value().iter() {
+                    let (desc, ident) = (Rc::from(desc.clone()), Rc::from(ident.clone()));
+                    en_map.as_ref().borrow_mut().insert(Some(Rc::clone(&desc)), Some(Rc::clone(&ident)));
+                    def.members.push((ident, *val, desc));
+                }
+                def.members.sort_by_key(|v| v.1);
+                self.enmap.as_ref().borrow_mut().insert(key, en_map);
+                self.model.enum_refs.push(def);
+            } else {
+                println!("cant find enum: {}.xlsx -> t_{}", unsafe { ENUM_LIBRARY_NAME }, name);
+            }
+            return;
+        }
+
+        let mut file_name = String::from(owner);
+        file_name.push('.');
+        file_name.push_str(DEFAULT_SOURCE_SUFFIX);
+        let xlsx_path = find_file(unsafe { SOURCE_XLSXS_DIR }, &file_name);
+        if let Ok(enum_table) = Self::get_table_with_id(xlsx_path, &format!("t_{}", name)) {
+            let (members, reserved, _) = Self::read_enum_members(&enum_table);
+            let en_map = ENMap::default();
+            for (ident, _, desc) in members.iter() {
+                en_map.as_ref().borrow_mut().insert(Some(desc.clone()), Some(ident.clone()));
+            }
+            self.enmap.as_ref().borrow_mut().insert(key, en_map);
+            self.model.enum_refs.push(EnumDef { owner: String::from(owner), name: String::from(name), members, reserved });
+        } else {
+            println!("cant find enum: {}.xlsx -> t_{}", owner, name);
+        }
+    }
+
+    fn parse_template(&mut self, table: ExcelTable, base_name: &str, refdata: Option<Arc<RefData>>) -> Result<()> {
         let width = table.width();
         let mut height = table.height();
         let ls_map: LSMap = Rc::from(RefCell::from(HashMap::with_capacity(64)));
@@ -331,18 +335,18 @@ impl Parser {
         let fk_value = FKValue::new(fk_data);
         fk_value.parse();
 
+        self.load_enum_refs(&table, base_name);
+
         let mut defkey_col = DATA_TEMPLATE_ID_POS.1;
-        // collect skip_cols and required fields and defkeys and enum flags
+        // collect skip_cols and defkeys
         for col in 0..width {
             if let Some(v) = table.cell(col, DATA_IDENTIFY_ROW) {
                 if v.starts_with('#') {
                     self.skip_cols.push(col);
                     if v.contains("DefKey") {
-                        self.key_type = Rc::from(RefCell::from(KeyType::DefKey(Vec::default())));
+                        self.model.key_type = KeyType::DefKey(Vec::default());
                         defkey_col = col;
                     }
-                } else {
-                    self.required_fields.as_ref().borrow_mut().push(Some(v.clone()));
                 }
             } else {
                 self.skip_cols.push(col);
@@ -385,7 +389,7 @@ impl Parser {
             if let Some(v) = table.cell(col, DATA_ENUM_FLAG_ROW) {
                 if !ENUM_FLAGS_FILTER.contains(v.as_str()) && v.chars().all(|c| c.is_alphabetic()) {
                     use std::collections::hash_map::Entry;
-                    match self.enumflags.as_ref().borrow_mut().entry(String::from(v.as_str())) {
+                    match self.model.enumflags.entry(String::from(v.as_str())) {
                         Entry::Occupied(mut e) => {
                             e.get_mut().push(ident.clone());
                         }
@@ -395,113 +399,88 @@ impl Parser {
                             e.insert(vec);
                         }
                     }
+                    ENUM_FLAGS.entry(String::from(v.as_str())).or_default()
+                        .push((String::from(ident.as_str()), String::from(ty.as_str()), String::from(base_name)));
                 }
             }
 
-            // collect (comment, identify, type) in row (1, 3, 4)
-            if let Some(c1) = table.cell(col, DATA_COMMENT_ROW) {
-                self.item_class.items.push((
-                    Some(c1.clone()),
-                    Some(ident.clone()),
-                    Some(ty.clone()),
-                ));
-            } else {
-                self.item_class.items.push((
-                    None,
-                    Some(ident.clone()),
-                    Some(ty.clone()),
-                ));
-            }
+            // collect (comment, identify, type, fk) in row (1, 3, 4, 5)
+            let mut column = Column {
+                comment: table.cell(col, DATA_COMMENT_ROW).cloned(),
+                ident: ident.clone(),
+                ty: ty.clone(),
+                fixed_len: fixed_len(table.cell(col, DATA_TYPE_ROW).unwrap()),
+                fk: table.cell(col, DATA_FOREIGN_KEY_ROW).filter(|v| v.starts_with('*')).map(|v| Rc::from(String::from(&v[1..]))),
+                fk_targets: fk_value.get_targets(col),
+                default: None,
+                values: Vec::with_capacity(height - DATA_START_ROW),
+            };
 
             // collect defaults
             if let Some(default) = table.cell(col, DATA_DEFAULT_ROW) {
-                if default.as_str() == "None" || default.is_empty() {
-                    self.nodefs.as_ref().borrow_mut().insert(ident.clone());
-                } else {
-                    use std::collections::hash_map::Entry;
-                    match self.defaults.as_ref().borrow_mut().0.entry(ident.clone()) {
-                        Entry::Occupied(_) => {}
-                        Entry::Vacant(e) => {
-                            let fk_default = fk_value.get_value(col, DATA_DEFAULT_ROW);
-                            if !fk_default.is_empty() {
-                                e.insert(Box::new(CellValue::new(&Rc::from(String::from(fk_default)), &ty, &ls_map, &ls_empty_map, &ident, 
-                                    &self.enmap, base_name, DATA_DEFAULT_ROW, col)));
-                            } else {
-                                e.insert(Box::new(CellValue::new(default, &ty, &ls_map, &ls_empty_map, &ident, 
-                                    &self.enmap, base_name, DATA_DEFAULT_ROW, col)));
-                            }
-                        }
+                if default.as_str() != "None" && !default.is_empty() {
+                    let fk_default = fk_value.get_value(col, DATA_DEFAULT_ROW);
+                    if !fk_default.is_empty() {
+                        column.default = Some(Box::new(CellValue::new(&Rc::from(String::from(fk_default)), &ty, &ls_map, &ls_empty_map, &ident, 
+                            &self.enmap, base_name, DATA_DEFAULT_ROW, col)));
+                    } else {
+                        column.default = Some(Box::new(CellValue::new(default, &ty, &ls_map, &ls_empty_map, &ident, 
+                            &self.enmap, base_name, DATA_DEFAULT_ROW, col)));
                     }
                 }
-            } else {
-                self.nodefs.as_ref().borrow_mut().insert(ident.clone());
             }
 
             // collect vars
-            if !self.vals.as_ref().borrow_mut().0.contains_key(ident) {
-                self.vals.as_ref().borrow_mut().0.insert(ident.clone(), Vec::default());
-                
-                for row in DATA_START_ROW..height-1 {
-                    use std::collections::hash_map::Entry;
-                    if let Some(v) = table.cell(col, row) {
-                        match self.vals.as_ref().borrow_mut().0.entry(ident.clone()) {
-                            Entry::Occupied(mut e) => {
-                                let fk_v = fk_value.get_value(col, row);
-                                if !fk_v.is_empty() {
-                                    e.get_mut().push(Box::new(CellValue::new(&Rc::from(String::from(fk_v)), &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
-                                } else {
-                                    e.get_mut().push(Box::new(CellValue::new(v, &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
-                                }
-                            }
-                            Entry::Vacant(_) => {}
-                        }
+            for row in DATA_START_ROW..height-1 {
+                if let Some(v) = table.cell(col, row) {
+                    let fk_v = fk_value.get_value(col, row);
+                    if !fk_v.is_empty() {
+                        column.values.push(Box::new(CellValue::new(&Rc::from(String::from(fk_v)), &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
                     } else {
-                        // empty cell
-                        match self.vals.as_ref().borrow_mut().0.entry(ident.clone()) {
-                            Entry::Occupied(mut e) => {
-                                if let Some(default) = table.cell(col, DATA_DEFAULT_ROW) {
-                                    let fk_default = fk_value.get_value(col, DATA_DEFAULT_ROW);
-                                    if !fk_default.is_empty() {
-                                        e.get_mut().push(Box::new(CellValue::new(&Rc::from(String::from(fk_default)), &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
-                                    } else {
-                                        e.get_mut().push(Box::new(CellValue::new(default, &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
-                                    }
-                                } else {
-                                    e.get_mut().push(Box::new(CellValue::new(&Rc::default(), &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
-                                }
-                            }
-                            Entry::Vacant(_) => {}
+                        column.values.push(Box::new(CellValue::new(v, &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
+                    }
+                } else {
+                    // empty cell
+                    if let Some(default) = table.cell(col, DATA_DEFAULT_ROW) {
+                        let fk_default = fk_value.get_value(col, DATA_DEFAULT_ROW);
+                        if !fk_default.is_empty() {
+                            column.values.push(Box::new(CellValue::new(&Rc::from(String::from(fk_default)), &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
+                        } else {
+                            column.values.push(Box::new(CellValue::new(default, &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
                         }
+                    } else {
+                        column.values.push(Box::new(CellValue::new(&Rc::default(), &ty, &ls_map, &ls_empty_map, &ident, &self.enmap, base_name, row, col)));
                     }
                 }
             }
+            self.model.columns.push(column);
+        }
+
+        // the enum cells not found are reported while parsing, they would be written as member 0
+        let unresolved = self.model.columns.iter()
+            .map(|v| v.values.iter().chain(v.default.iter()).filter(|v| v.is_unresolved_enum()).count())
+            .sum::<usize>();
+        if unresolved > 0 {
+            return Err(Error::new(ErrorKind::InvalidData, format!("{}: {} enum values not found", base_name, unresolved)));
         }
 
-        // item_class
-        self.item_class.defaults = Some(Rc::downgrade(&self.defaults));
-        self.item_class.vals = Some(Rc::downgrade(&self.vals));
-        self.item_class.enmaps = Some(Rc::downgrade(&self.enmap));
-        self.item_class.enumflags = Some(Rc::downgrade(&self.enumflags));
-        // base_class
-        self.base_class.lines = height - DATA_START_ROW - 1;
-        self.base_class.defaults = Some(Rc::downgrade(&self.defaults));
-        self.base_class.vals = Some(Rc::downgrade(&self.vals));
-        self.base_class.required_fields = Some(Rc::downgrade(&self.required_fields));
-        self.base_class.keytypes = Some(Rc::downgrade(&self.key_type));
-        self.base_class.nodefs = Rc::downgrade(&self.nodefs);
-        self.base_class.enumflags = Some(Rc::downgrade(&self.enumflags));
+        self.model.lines = height - DATA_START_ROW - 1;
         if let Some(v) = table.cell(0, 4) {
-            self.base_class.id_type = v.clone();
+            self.model.id_type = v.clone();
+        }
+        for row in DATA_START_ROW..height-1 {
+            self.model.ref_names.push(table.cell(0, row).cloned().unwrap_or_default());
         }
 
         // collect DefKey in col 1, data start frow row 8
-        if let KeyType::DefKey(ref mut vec) = *self.key_type.as_ref().borrow_mut() {
+        if let KeyType::DefKey(ref mut vec) = self.model.key_type {
             for row in DATA_START_ROW..height-1 {
                 if let (Some(v0), Some(v1)) = (table.cell(0, row), table.cell(defkey_col, row)) {
                     vec.push((Some(v1.clone()), row - DATA_START_ROW, Some(v0.clone())));
                 }
             }
         }
+        Ok(())
     }
 
     fn pre_process_lstring<'a>(ls_map: &LSMap, val: &str, is_trivial: bool, ls_seed: &'a mut i32, ls_empty_map: &mut LSEmptyMap, row: usize, col: usize) {
@@ -564,6 +543,276 @@ impl Parser {
     }
 }
 
+// rows whose identifier starts with '#' are retired, their values are reserved and never reused;
+// blank values are auto-increased from the previous member
+fn enum_members<'a, I>(rows: I) -> (Vec<EnumMember>, BTreeSet<i32>, Vec<String>)
+where I: Iterator<Item = (Option<&'a Rc<String>>, Option<&'a Rc<String>>, Option<&'a Rc<String>>)>
+{
+    let mut members = Vec::<EnumMember>::default();
+    let mut reserved = BTreeSet::<i32>::default();
+    let mut errs = Vec::<String>::default();
+    let mut next = 0;
+
+    for (row, (ident, val, desc)) in rows.enumerate() {
+        let ident = if let Some(v) = ident { v } else { continue; };
+        let val = match val.map(|v| v.trim().parse::<i32>()) {
+            Some(Ok(v)) => v,
+            Some(Err(_)) => {
+                errs.push(format!("row {}: value of {} is not an integer", row + 1, ident));
+                continue;
+            }
+            None if ident.starts_with('#') => {
+                errs.push(format!("row {}: retired member {} has no value", row + 1, ident));
+                continue;
+            }
+            None => {
+                let mut v = next;
+                while reserved.contains(&v) { v += 1; }
+                v
+            }
+        };
+
+        if ident.starts_with('#') {
+            reserved.insert(val);
+            continue;
+        }
+
+        if !is_valid_ident(ident) {
+            errs.push(format!("row {}: {} is not a valid identifier", row + 1, ident));
+        }
+        let desc = desc.unwrap_or(ident);
+        for (i, v, d) in members.iter() {
+            if i == ident {
+                errs.push(format!("row {}: duplicate identifier {}", row + 1, ident));
+            }
+            if *v == val {
+                errs.push(format!("row {}: {} = {} duplicates the value of {}", row + 1, ident, val, i));
+            }
+            if d == desc {
+                errs.push(format!("row {}: {} duplicates the description of {}: {}", row + 1, ident, i, desc));
+            }
+        }
+
+        next = val + 1;
+        members.push((ident.clone(), val, desc.clone()));
+    }
+
+    for (ident, val, _) in members.iter() {
+        if reserved.contains(val) {
+            errs.push(format!("{} = {} reuses a reserved value", ident, val));
+        }
+    }
+    (members, reserved, errs)
+}
+
+// compare with the previous build, removed or renumbered members break the saved data
+fn check_prev_enum(prev: Option<(Vec<(String, i32)>, BTreeSet<i32>)>, members: &Vec<EnumMember>, reserved: &mut BTreeSet<i32>, errs: &mut Vec<String>) {
+    let (prev, prev_reserved) = if let Some(v) = prev { v } else { return; };
+    for val in prev_reserved.into_iter() {
+        // the ones of the sheet are checked while reading it
+        if !reserved.insert(val) {
+            continue;
+        }
+        if let Some((ident, _, _)) = members.iter().find(|v| v.1 == val) {
+            errs.push(format!("{} = {} reuses a reserved value", ident, val));
+        }
+    }
+
+    for (ident, val) in prev.iter() {
+        match members.iter().find(|v| v.0.as_str() == ident) {
+            Some((_, v, _)) if v != val => {
+                errs.push(format!("breaking: {} changed from {} to {}", ident, val, v));
+            }
+            None if !reserved.contains(val) => {
+                errs.push(format!("breaking: {} = {} was removed, retire it with #{} instead", ident, val, ident));
+            }
+            _ => {}
+        }
+    }
+}
+
+// members and reserved values of the previous build of an enum, whatever was exported
+fn read_prev_enum(type_name: &str) -> Option<(Vec<(String, i32)>, BTreeSet<i32>)> {
+    let path = format!("{}/{}.{}", unsafe { REF_TEXT_DIR }, type_name, DEFAULT_ENUM_HISTORY_SUFFIX);
+    match fs::read_to_string(path) {
+        Ok(content) => Some(parse_enum_history(&content)),
+        // builds before the history only left the C# enum
+        Err(_) => read_prev_cs_enum(&format!("{}/{}.cs", unsafe { OUTPUT_ENUM_CODE_DIR }, type_name)),
+    }
+}
+
+// ident and value on a line each like ref.txt, the reserved values under '#'
+fn parse_enum_history(content: &str) -> (Vec<(String, i32)>, BTreeSet<i32>) {
+    let mut members = Vec::<(String, i32)>::default();
+    let mut reserved = BTreeSet::<i32>::default();
+    let lines = content.lines().map(|v| v.trim()).filter(|v| !v.is_empty()).collect::<Vec<_>>();
+
+    for pair in lines.chunks(2) {
+        if let [ident, val] = pair {
+            match val.parse::<i32>() {
+                Ok(val) if *ident == "#" => { reserved.insert(val); }
+                Ok(val) => members.push((String::from(*ident), val)),
+                Err(_) => println!("parse failed: src = {}", val),
+            }
+        }
+    }
+    (members, reserved)
+}
+
+fn write_enum_history(type_name: &str, members: &Vec<EnumMember>, reserved: &BTreeSet<i32>) -> Result<()> {
+    let dir = unsafe { REF_TEXT_DIR };
+    fs::create_dir_all(dir)?;
+    let mut file = File::create(format!("{}/{}.{}", dir, type_name, DEFAULT_ENUM_HISTORY_SUFFIX))?;
+    for (ident, val, _) in members.iter() {
+        file.write_fmt(format_args!("{}{}{}{}", ident, LINE_END_FLAG, val, LINE_END_FLAG))?;
+    }
+    for val in reserved.iter() {
+        file.write_fmt(format_args!("#{}{}{}", LINE_END_FLAG, val, LINE_END_FLAG))?;
+    }
+    Ok(())
+}
+
+// members and reserved values of a previously generated C# enum file
+fn read_prev_cs_enum(path: &str) -> Option<(Vec<(String, i32)>, BTreeSet<i32>)> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut members = Vec::<(String, i32)>::default();
+    let mut reserved = BTreeSet::<i32>::default();
+
+    for line in content.lines().map(|v| v.trim()) {
+        if let Some(vals) = line.strip_prefix(ENUM_RESERVED_FLAG) {
+            vals.split(',').filter_map(|v| v.trim().parse::<i32>().ok()).for_each(|v| { reserved.insert(v); });
+        } else if let Some((ident, val)) = line.strip_suffix(',').and_then(|v| v.split_once(" = ")) {
+            if let Ok(val) = val.parse::<i32>() {
+                members.push((String::from(ident), val));
+            }
+        }
+    }
+    Some((members, reserved))
+}
+
+// E{Name}ReferencedType: union of the enumflags columns of every table, values stay stable across builds.
+// a member missing from the build is reserved for good, so it runs only when every table parsed
+pub fn gen_referenced_types() -> Result<Vec<EnumDef>> {
+    let mut ret = Vec::<EnumDef>::default();
+    let mut failed = Vec::<String>::default();
+    let mut groups = ENUM_FLAGS.iter().map(|v| (v.key().clone(), v.value().clone())).collect::<Vec<_>>();
+    groups.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (group, refs) in groups.iter() {
+        let type_name = naming::enum_type("", &format!("{}ReferencedType", group));
+        if let Some(other) = ENUM_TYPES.get(&type_name) {
+            println!("{}: already the enum of {}.xlsx", type_name, other.value());
+            failed.push(type_name);
+            continue;
+        }
+        match referenced_type(group, refs, read_prev_enum(&type_name)) {
+            Ok(def) => {
+                write_enum_history(&type_name, &def.members, &def.reserved)?;
+                ret.push(def);
+            }
+            Err(errs) => {
+                for e in errs.iter() {
+                    println!("{}: {}", type_name, e);
+                }
+                failed.push(type_name);
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(ret)
+    } else {
+        Err(Error::new(ErrorKind::InvalidData, format!("invalid enum: {}", failed.join(", "))))
+    }
+}
+
+// the enum of one group from its (ident, type, table) columns and the previous build
+fn referenced_type(group: &str, refs: &Vec<(String, String, String)>, prev: Option<(Vec<(String, i32)>, BTreeSet<i32>)>) -> std::result::Result<EnumDef, Vec<String>> {
+    const INT_TYPES: [&str; 5] = ["sbyte", "byte", "short", "ushort", "int"];
+    let mut errs = Vec::<String>::default();
+    // <ident, (type, tables)>
+    let mut fields = BTreeMap::<&str, (&str, Vec<&str>)>::default();
+    for (ident, ty, table) in refs.iter() {
+        if !INT_TYPES.contains(&ty.as_str()) {
+            errs.push(format!("{}.{} is {}, Get{}BonusInt can't return it", table, ident, ty, group));
+        }
+        match fields.get_mut(ident.as_str()) {
+            Some((prev_ty, tables)) => {
+                if *prev_ty != ty.as_str() {
+                    errs.push(format!("{} is {} in {} but {} in {}", ident, prev_ty, tables.join(", "), ty, table));
+                }
+                if !tables.contains(&table.as_str()) {
+                    tables.push(table);
+                }
+            }
+            None => {
+                fields.insert(ident, (ty, vec![table.as_str()]));
+            }
+        }
+    }
+    let mut lowers = HashMap::<String, &str>::default();
+    for ident in fields.keys() {
+        if let Some(other) = lowers.insert(ident.to_lowercase(), ident) {
+            errs.push(format!("{} and {} differ only in case", other, ident));
+        }
+    }
+    if !errs.is_empty() {
+        return Err(errs);
+    }
+
+    // previous members keep their values, dropped ones are reserved, new ones are appended
+    // descriptions name the field too, they are keys of the helper like those of the sheets
+    let desc = |ident: &str, tables: &Vec<&str>| Rc::new(format!("{} ({})", ident, tables.join(", ")));
+    let (prev, mut reserved) = prev.unwrap_or_default();
+    let mut next = prev.iter().map(|v| v.1 + 1).chain(reserved.iter().map(|v| v + 1)).max().unwrap_or(0);
+    let mut members = Vec::<EnumMember>::with_capacity(fields.len());
+    for (ident, val) in prev.into_iter() {
+        if let Some((_, tables)) = fields.remove(ident.as_str()) {
+            let desc = desc(&ident, &tables);
+            members.push((Rc::new(ident), val, desc));
+        } else {
+            reserved.insert(val);
+        }
+    }
+    for (ident, (_, tables)) in fields.into_iter() {
+        members.push((Rc::new(String::from(ident)), next, desc(ident, &tables)));
+        next = next + 1;
+    }
+
+    Ok(EnumDef { owner: String::default(), name: format!("{}ReferencedType", group), members, reserved })
+}
+
+// C# keywords, not usable as member names without '@'
+const CS_KEYWORDS: [&str; 77] = [
+    "abstract", "as", "base", "bool", "break", "byte", "case", "catch", "char", "checked", "class", "const",
+    "continue", "decimal", "default", "delegate", "do", "double", "else", "enum", "event", "explicit", "extern",
+    "false", "finally", "fixed", "float", "for", "foreach", "goto", "if", "implicit", "in", "int", "interface",
+    "internal", "is", "lock", "long", "namespace", "new", "null", "object", "operator", "out", "override",
+    "params", "private", "protected", "public", "readonly", "ref", "return", "sbyte", "sealed", "short",
+    "sizeof", "stackalloc", "static", "string", "struct", "switch", "this", "throw", "true", "try", "typeof",
+    "uint", "ulong", "unchecked", "unsafe", "ushort", "using", "virtual", "void", "volatile", "while",
+];
+
+fn is_valid_ident(v: &str) -> bool {
+    v != "Count" && !CS_KEYWORDS.contains(&v) &&
+    v.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_') &&
+    v.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+// enum:Name -> (Name, base_name, Name), enum:Other.Name -> (Other.Name, Other, Name)
+// enums of the library are owned by "" -> (EnumLibrary.Name, "", Name)
+pub(crate) fn enum_ref<'a>(ty: &'a str, base_name: &'a str) -> (String, &'a str, &'a str) {
+    let v = ty.strip_prefix("enum:").unwrap_or(ty);
+    let library = unsafe { ENUM_LIBRARY_NAME };
+    match v.split_once('.') {
+        Some((owner, name)) if owner == library => (format!("{}.{}", library, name), "", name),
+        Some((owner, name)) if owner != base_name => (String::from(v), owner, name),
+        Some((_, name)) => (String::from(name), base_name, name),
+        None if ENUM_LIB.contains_key(v) => (format!("{}.{}", library, v), "", v),
+        None => (String::from(v), base_name, v)
+    }
+}
+
 fn convert_type(v: &mut String) {
     if let Some(idx) = v.find('[') {
         let mut n = idx;
@@ -578,6 +827,13 @@ fn convert_type(v: &mut String) {
     }
 }
 
+// int[3] -> 3
+fn fixed_len(v: &str) -> Option<usize> {
+    let start = v.find('[')?;
+    let len = v[start..].find(']')?;
+    v[start + 1..start + len].trim().parse().ok()
+}
+
 pub fn find_file<P: AsRef<Path>>(dir: P, filename: &str) -> PathBuf {
     let dir = dir.as_ref();
 
@@ -599,4 +855,157 @@ pub fn find_file<P: AsRef<Path>>(dir: P, filename: &str) -> PathBuf {
     } else {
         PathBuf::default()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (ident, val, desc) of a t_ sheet, "" is an empty cell
+    fn sheet(rows: &[(&str, &str, &str)]) -> Vec<[Option<Rc<String>>; 3]> {
+        let cell = |v: &str| if v.is_empty() { None } else { Some(Rc::new(String::from(v))) };
+        rows.iter().map(|(i, v, d)| [cell(i), cell(v), cell(d)]).collect()
+    }
+
+    fn read_sheet(rows: &[(&str, &str, &str)]) -> (Vec<EnumMember>, BTreeSet<i32>, Vec<String>) {
+        let rows = sheet(rows);
+        enum_members(rows.iter().map(|[i, v, d]| (i.as_ref(), v.as_ref(), d.as_ref())))
+    }
+
+    fn prev(members: &[(&str, i32)], reserved: &[i32]) -> Option<(Vec<(String, i32)>, BTreeSet<i32>)> {
+        Some((members.iter().map(|(i, v)| (String::from(*i), *v)).collect(), reserved.iter().copied().collect()))
+    }
+
+    #[test]
+    fn blank_values_follow_the_previous_member() {
+        let (members, reserved, errs) = read_sheet(&[("Fire", "", ""), ("Water", "5", "水"), ("Wind", "", "")]);
+        assert!(errs.is_empty(), "{:?}", errs);
+        assert!(reserved.is_empty());
+        let vals = members.iter().map(|v| (v.0.as_str(), v.1, v.2.as_str())).collect::<Vec<_>>();
+        assert_eq!(vals, vec![("Fire", 0, "Fire"), ("Water", 5, "水"), ("Wind", 6, "Wind")]);
+    }
+
+    #[test]
+    fn retired_values_are_reserved_and_skipped() {
+        let (members, reserved, errs) = read_sheet(&[("#Old", "1", ""), ("Fire", "0", ""), ("Water", "", "")]);
+        assert!(errs.is_empty(), "{:?}", errs);
+        assert_eq!(reserved, BTreeSet::from([1]));
+        assert_eq!(members.iter().map(|v| v.1).collect::<Vec<_>>(), vec![0, 2]);
+
+        let (_, _, errs) = read_sheet(&[("#Old", "1", ""), ("Fire", "1", "")]);
+        assert_eq!(errs, vec![String::from("Fire = 1 reuses a reserved value")]);
+        let (_, _, errs) = read_sheet(&[("#Old", "", "")]);
+        assert_eq!(errs.len(), 1);
+    }
+
+    #[test]
+    fn duplicates_and_bad_identifiers_are_errors() {
+        let (_, _, errs) = read_sheet(&[("Fire", "0", "火"), ("Fire", "1", "水")]);
+        assert_eq!(errs, vec![String::from("row 2: duplicate identifier Fire")]);
+        let (_, _, errs) = read_sheet(&[("Fire", "0", "火"), ("Water", "0", "水")]);
+        assert_eq!(errs, vec![String::from("row 2: Water = 0 duplicates the value of Fire")]);
+        let (_, _, errs) = read_sheet(&[("Fire", "0", "火"), ("Water", "1", "火")]);
+        assert_eq!(errs, vec![String::from("row 2: Water duplicates the description of Fire: 火")]);
+        let (_, _, errs) = read_sheet(&[("Fire", "x", "")]);
+        assert_eq!(errs, vec![String::from("row 1: value of Fire is not an integer")]);
+
+        for ident in ["1st", "Count", "a-b", "class", "default", "int"] {
+            let (_, _, errs) = read_sheet(&[(ident, "0", "")]);
+            assert_eq!(errs, vec![format!("row 1: {} is not a valid identifier", ident)]);
+        }
+        assert!(is_valid_ident("_Class") && is_valid_ident("Class") && is_valid_ident("火"));
+    }
+
+    #[test]
+    fn previous_members_may_only_be_retired() {
+        let (fire_water, _, _) = read_sheet(&[("Fire", "0", ""), ("Water", "1", "")]);
+        let mut reserved = BTreeSet::default();
+        let mut errs = Vec::default();
+        check_prev_enum(prev(&[("Fire", 0), ("Water", 1)], &[]), &fire_water, &mut reserved, &mut errs);
+        assert!(errs.is_empty(), "{:?}", errs);
+
+        // removed
+        let (fire, mut reserved, _) = read_sheet(&[("Fire", "0", "")]);
+        check_prev_enum(prev(&[("Fire", 0), ("Water", 1)], &[]), &fire, &mut reserved, &mut errs);
+        assert_eq!(errs, vec![String::from("breaking: Water = 1 was removed, retire it with #Water instead")]);
+
+        // retired
+        let (fire, mut reserved, mut errs) = read_sheet(&[("Fire", "0", ""), ("#Water", "1", "")]);
+        check_prev_enum(prev(&[("Fire", 0), ("Water", 1)], &[]), &fire, &mut reserved, &mut errs);
+        assert!(errs.is_empty(), "{:?}", errs);
+        assert_eq!(reserved, BTreeSet::from([1]));
+
+        // renumbered
+        let (moved, mut reserved, mut errs) = read_sheet(&[("Fire", "0", ""), ("Water", "2", "")]);
+        check_prev_enum(prev(&[("Fire", 0), ("Water", 1)], &[]), &moved, &mut reserved, &mut errs);
+        assert_eq!(errs, vec![String::from("breaking: Water changed from 1 to 2")]);
+    }
+
+    #[test]
+    fn values_reserved_by_a_previous_build_stay_reserved() {
+        // the retired row was deleted from the sheet since
+        let (fire, mut reserved, mut errs) = read_sheet(&[("Fire", "0", "")]);
+        check_prev_enum(prev(&[("Fire", 0)], &[1]), &fire, &mut reserved, &mut errs);
+        assert!(errs.is_empty(), "{:?}", errs);
+        assert_eq!(reserved, BTreeSet::from([1]));
+
+        let (reused, mut reserved, mut errs) = read_sheet(&[("Fire", "0", ""), ("Wind", "1", "")]);
+        check_prev_enum(prev(&[("Fire", 0)], &[1]), &reused, &mut reserved, &mut errs);
+        assert_eq!(errs, vec![String::from("Wind = 1 reuses a reserved value")]);
+    }
+
+    fn flags(cols: &[(&str, &str, &str)]) -> Vec<(String, String, String)> {
+        cols.iter().map(|(i, t, tab)| (String::from(*i), String::from(*t), String::from(*tab))).collect()
+    }
+
+    fn values(def: &EnumDef) -> Vec<(&str, i32)> {
+        def.members.iter().map(|v| (v.0.as_str(), v.1)).collect()
+    }
+
+    #[test]
+    fn referenced_types_keep_the_values_of_the_previous_build() {
+        let def = referenced_type("Power", &flags(&[("Def", "int", "Item"), ("Atk", "int", "Item")]), None).unwrap();
+        assert_eq!(values(&def), vec![("Atk", 0), ("Def", 1)]);
+
+        // new columns are appended whatever their names, tables in any order
+        let cols = flags(&[("Spd", "int", "Skill"), ("Def", "int", "Skill"), ("Atk", "int", "Item"), ("Def", "int", "Item"), ("Agi", "short", "Skill")]);
+        let def = referenced_type("Power", &cols, prev(&[("Atk", 0), ("Def", 1)], &[])).unwrap();
+        assert_eq!(values(&def), vec![("Atk", 0), ("Def", 1), ("Agi", 2), ("Spd", 3)]);
+        assert_eq!(def.members[1].2.as_str(), "Def (Skill, Item)");
+        assert!(def.reserved.is_empty());
+    }
+
+    #[test]
+    fn referenced_types_of_one_table_have_distinct_descriptions() {
+        let def = referenced_type("Power", &flags(&[("Def", "int", "Item"), ("Atk", "int", "Item")]), None).unwrap();
+        let descs = def.members.iter().map(|v| v.2.as_str()).collect::<Vec<_>>();
+        assert_eq!(descs, vec!["Atk (Item)", "Def (Item)"]);
+    }
+
+    #[test]
+    fn dropped_referenced_types_are_reserved() {
+        let def = referenced_type("Power", &flags(&[("Def", "int", "Item")]), prev(&[("Atk", 0), ("Def", 1)], &[])).unwrap();
+        assert_eq!(values(&def), vec![("Def", 1)]);
+        assert_eq!(def.reserved, BTreeSet::from([0]));
+
+        let def = referenced_type("Power", &flags(&[("Def", "int", "Item"), ("Spd", "int", "Item")]), prev(&[("Def", 1)], &[0, 5])).unwrap();
+        assert_eq!(values(&def), vec![("Def", 1), ("Spd", 6)]);
+        assert_eq!(def.reserved, BTreeSet::from([0, 5]));
+    }
+
+    #[test]
+    fn referenced_types_of_tables_disagreeing_are_errors() {
+        let errs = referenced_type("Power", &flags(&[("Atk", "int", "Item"), ("Atk", "short", "Skill")]), None).err().unwrap();
+        assert_eq!(errs, vec![String::from("Atk is int in Item but short in Skill")]);
+        let errs = referenced_type("Power", &flags(&[("Atk", "int", "Item"), ("ATK", "int", "Skill")]), None).err().unwrap();
+        assert_eq!(errs, vec![String::from("ATK and Atk differ only in case")]);
+        let errs = referenced_type("Power", &flags(&[("Atk", "float", "Item")]), None).err().unwrap();
+        assert_eq!(errs, vec![String::from("Item.Atk is float, GetPowerBonusInt can't return it")]);
+    }
+
+    #[test]
+    fn enum_history_reads_ref_txt_pairs() {
+        let (members, reserved) = parse_enum_history("Fire\r\n0\r\nWater\r\n1\r\n#\r\n2\r\n#\r\n5\r\n");
+        assert_eq!(members, vec![(String::from("Fire"), 0), (String::from("Water"), 1)]);
+        assert_eq!(reserved, BTreeSet::from([2, 5]));
+    }
+}
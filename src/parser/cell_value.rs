@@ -1,23 +1,6 @@
-use std::{rc::Rc, io::{Write, Result}, cell::RefCell, collections::HashMap, vec};
+use std::{rc::Rc, cell::RefCell, collections::HashMap, vec};
 use super::{stack::Stack, LSMap, ENMap, fk_value::split_val, LSEmptyMap};
 
-macro_rules! get_basic_type_string {
-    ($self:ident, $stream:ident, $($enum:ident::$variant:ident),+) => {
-        match $self {
-            $( $enum::$variant(v) => v.ty($stream) ),+,
-            _ => Ok(())
-        }
-    };
-}
-
-macro_rules! gen_code {
-    ($self:ident, $stream:ident, $($enum:ident::$variant:ident),+) => {
-        match $self {
-            $( $enum::$variant(v) => v.value($stream) ),+,
-        }
-    };
-}
-
 macro_rules! define_getters {
     ($name1:ident, $name2:ident, $variant:ident, $type:ty) => {
         fn $name1(self) -> Option<$type> {
@@ -36,15 +19,6 @@ macro_rules! define_getters {
     };
 }
 
-macro_rules! write_value_to_stream {
-    ($vv:ident, $stream:ident, $($enum:ident::$variant:ident),+) => {
-        match $vv {
-            $( $enum::$variant(v) => { v.value($stream)?; } ),+,
-            _ => { $stream.write("".as_bytes())?; }
-        }
-    };
-}
-
 pub enum CellValue {
     DEnum(EnumValue),
     DBool(BoolValue),
@@ -166,11 +140,19 @@ impl CellValue {
                     let binding = map.borrow();
                     let v = binding.get(&Some(val.clone()));
                     if v.is_none() {
+                        println!("enum value {} not found in {}: {}", val_str, ident, base_name);
                         Self::DEnum(EnumValue(ident.clone(), Rc::default(), Rc::default()))
                     } else {
                         Self::DEnum(EnumValue(ident.clone(), v.as_ref().unwrap().as_ref().unwrap().clone(), Rc::from(String::from(base_name))))
                     }
-                }).unwrap_or(Self::DEnum(EnumValue(Rc::default(), Rc::default(), Rc::default())))
+                }).unwrap_or_else(|| {
+                    println!("cant find enum: {}.xlsx -> t_{}", base_name, ident);
+                    Self::DEnum(EnumValue(Rc::default(), Rc::default(), Rc::default()))
+                })
+            }
+            // enum:Name, enum:Base.Name, List<enum:..>, enum:..[]
+            s if s.starts_with("enum:") || s.starts_with("List<enum:") => {
+                Self::new_enum_ref(val_str, s, enmaps, base_name)
             }
             "ShortList" => {
                 let mut ret = Self::DShortList(ShortListValue::default());
@@ -305,6 +287,7 @@ impl CellValue {
 
                 obj_stack.pop().map(|mut v| {
                     collect_value(val, &mut v, ls_map, ls_empty_map, &pos);
+                    v.resolve_enums(enmaps, base_name);
                     v
                 }).unwrap()
             }
@@ -436,6 +419,7 @@ impl CellValue {
 
                 obj_stack.pop().map(|mut v| {
                     collect_value(val, &mut v, ls_map, ls_empty_map, &pos);
+                    v.resolve_enums(enmaps, base_name);
                     v
                 }).unwrap()
             }
@@ -485,6 +469,7 @@ impl CellValue {
                 
                 if op_stack.is_empty() {
                     collect_value(val, &mut ret, &ls_map, ls_empty_map, &pos);
+                    ret.resolve_enums(enmaps, base_name);
                     ret
                 } else {
                     // TODO: err
@@ -498,33 +483,6 @@ impl CellValue {
         }
     }
 
-    pub fn gen_code<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        gen_code!(
-            self,
-            stream,
-            CellValue::DEnum,
-            CellValue::DBool,
-            CellValue::DByte, 
-            CellValue::DSByte, 
-            CellValue::DInt, 
-            CellValue::DUInt,
-            CellValue::DFloat,
-            CellValue::DDouble,
-            CellValue::DShort, 
-            CellValue::DUShort, 
-            CellValue::DString,
-            CellValue::DLString,
-            CellValue::DCustom,
-            CellValue::DShortList,
-            CellValue::DTuple,
-            CellValue::DValueTuple,
-            CellValue::DArray,
-            CellValue::DList,
-            CellValue::DNone,
-            CellValue::DError
-        )
-    }
-
     pub fn is_enum(&self) -> bool {
         if let Self::DEnum(_) = self {
             true
@@ -533,6 +491,16 @@ impl CellValue {
         }
     }
 
+    // an enum cell, or element, whose value is not a member of its enum
+    pub fn is_unresolved_enum(&self) -> bool {
+        match self {
+            Self::DEnum(v) => v.1.is_empty(),
+            Self::DList(ListValue(data)) | Self::DArray(ArrayValue(data)) => data.iter().skip(1).any(|v| v.is_unresolved_enum()),
+            Self::DValueTuple(ValueTupleValue(data)) | Self::DTuple(TupleValue(data)) => data.iter().any(|v| v.is_unresolved_enum()),
+            _ => false
+        }
+    }
+
     pub fn is_lstring(&self) -> bool {
         if let Self::DLString(_) = self {
             true
@@ -594,6 +562,7 @@ impl CellValue {
             "string" => Self::DString(StringValue::default()),
             "LString" | "Lstring" => Self::DLString(LStringValue::default()),
             "ShortList" => Self::DShortList(ShortListValue::default()),
+            s if s.starts_with("enum:") && !s.ends_with("[]") => Self::DEnum(EnumValue::default()),
             s if s.contains("Tuple") => {
                 let mut ch_stack = Stack::<char>::new();
                 // 0-List, 1-Tuple
@@ -807,27 +776,66 @@ impl CellValue {
         }
         s.chars().rev().collect()
     }
+
+    fn new_enum_ref(val: &str, ty: &str, enmaps: &Rc<RefCell<HashMap<String, ENMap>>>, base_name: &str) -> Self {
+        // None-scalar, Some(true)-List, Some(false)-Array
+        let (inner, is_list) = if let Some(v) = ty.strip_prefix("List<").and_then(|v| v.strip_suffix('>')) {
+            (v, Some(true))
+        } else if let Some(v) = ty.strip_suffix("[]") {
+            (v, Some(false))
+        } else {
+            (ty, None)
+        };
+        let (key, owner, name) = super::enum_ref(inner, base_name);
+        let name: Rc<String> = Rc::from(String::from(name));
+        let owner: Rc<String> = Rc::from(String::from(owner));
+
+        // cell value may be the description or the identifier of the member
+        let resolve = |v: &str| -> CellValue {
+            let ident = enmaps.borrow().get(&key).and_then(|map| {
+                let binding = map.borrow();
+                if let Some(Some(ident)) = binding.get(&Some(Rc::from(String::from(v)))) {
+                    Some(ident.clone())
+                } else {
+                    binding.values().flatten().find(|ident| ident.as_str() == v).cloned()
+                }
+            });
+            if ident.is_none() {
+                println!("enum value {} not found in {}: {}", v, key, base_name);
+            }
+            CellValue::DEnum(EnumValue(name.clone(), ident.unwrap_or_default(), owner.clone()))
+        };
+
+        match is_list {
+            Some(is_list) => {
+                let mut data = vec![CellValue::DEnum(EnumValue(name.clone(), Rc::default(), owner.clone()))];
+                let filter_val: String = val.chars().filter(|c| !c.is_whitespace() && *c != '{' && *c != '}').collect();
+                for v in filter_val.split(',').filter(|v| !v.is_empty()) {
+                    data.push(resolve(v));
+                }
+                if is_list { Self::DList(ListValue(data)) } else { Self::DArray(ArrayValue(data)) }
+            }
+            None => resolve(val)
+        }
+    }
     
-    fn get_basic_type_string<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        get_basic_type_string!(
-            self,
-            stream,
-            CellValue::DBool, 
-            CellValue::DByte, 
-            CellValue::DSByte, 
-            CellValue::DInt, 
-            CellValue::DUInt, 
-            CellValue::DShort, 
-            CellValue::DUShort,
-            CellValue::DFloat,
-            CellValue::DDouble,
-            CellValue::DString,
-            CellValue::DLString,
-            CellValue::DShortList,
-            CellValue::DCustom,
-            CellValue::DTuple,
-            CellValue::DValueTuple
-        )
+    // enum:.. elements of tuples against the enum maps, the dumb elements of lists only get their type
+    fn resolve_enums(&mut self, enmaps: &Rc<RefCell<HashMap<String, ENMap>>>, base_name: &str) {
+        match self {
+            Self::DEnum(EnumValue(ty, raw, _)) if ty.starts_with("enum:") => {
+                if raw.is_empty() {
+                    let (_, owner, name) = super::enum_ref(ty, base_name);
+                    *self = Self::DEnum(EnumValue(Rc::from(String::from(name)), Rc::default(), Rc::from(String::from(owner))));
+                } else {
+                    let (ty, raw) = (ty.clone(), raw.clone());
+                    *self = Self::new_enum_ref(&raw, &ty, enmaps, base_name);
+                }
+            }
+            Self::DList(ListValue(data)) | Self::DArray(ArrayValue(data)) | Self::DValueTuple(ValueTupleValue(data)) | Self::DTuple(TupleValue(data)) => {
+                data.iter_mut().for_each(|v| v.resolve_enums(enmaps, base_name));
+            }
+            _ => {}
+        }
     }
 
     // not include list and array
@@ -845,6 +853,8 @@ impl CellValue {
             "byte" => CellValue::DByte(ByteValue(0)),
             "bool" => CellValue::DBool(BoolValue(true)),
             "ShortList" => CellValue::DShortList(ShortListValue::default()),
+            // keeps the type until resolve_enums, the value is the raw cell
+            k if k.starts_with("enum:") => CellValue::DEnum(EnumValue(Rc::from(String::from(k)), Rc::default(), Rc::default())),
             "" => CellValue::DError(ErrorValue),
             custom => CellValue::DCustom(CustomValue(Rc::from(String::from(custom)), Rc::default()))
         }
@@ -885,6 +895,9 @@ impl CellValue {
             CellValue::DUShort(_) => {
                 CellValue::DUShort(UShortValue(0))
             },
+            CellValue::DEnum(d) => {
+                CellValue::DEnum(EnumValue(d.0.clone(), Rc::default(), Rc::default()))
+            },
             CellValue::DCustom(d) => {
                 CellValue::DCustom(CustomValue(d.0.clone(), Rc::default()))
             },
@@ -1013,6 +1026,13 @@ fn collect_basic_value(e: &str, arr: &mut Vec<CellValue>, ls_map: &LSMap, ls_emp
                 //println!("{}: src val= {}", err, e);
             }
         }
+        // raw text until resolve_enums
+        CellValue::DEnum(EnumValue(ref ty, _, _)) => {
+            if !e.is_empty() {
+                let ty = ty.clone();
+                arr.push(CellValue::DEnum(EnumValue(ty, Rc::new(e.to_string()), Rc::default())));
+            }
+        }
         _ => { todo!("err") }
     }
 }
@@ -1058,6 +1078,7 @@ fn collect_vec_value(arr: &mut Vec<CellValue>, ls_map: &LSMap, filter_val: &Stri
                     CellValue::DShort(ShortValue(_)) => { tuple.0.push(CellValue::DShort(ShortValue(vs[idx].parse::<i16>().unwrap()))) }
                     CellValue::DUShort(UShortValue(_)) => { tuple.0.push(CellValue::DUShort(UShortValue(vs[idx].parse::<u16>().unwrap()))) }
                     CellValue::DString(StringValue(_)) => { tuple.0.push(CellValue::DString(StringValue(Rc::from(String::from(&vs[idx]))))) }
+                    CellValue::DEnum(EnumValue(ty, _, _)) => { tuple.0.push(CellValue::DEnum(EnumValue(ty.clone(), Rc::from(String::from(&vs[idx])), Rc::default()))) }
                     CellValue::DLString(LStringValue(_, _)) => {
                         let key = Rc::from(String::from(&vs[idx]));
                         if ls_data.contains_key(&key) {
@@ -1099,6 +1120,7 @@ fn collect_vec_value(arr: &mut Vec<CellValue>, ls_map: &LSMap, filter_val: &Stri
                     CellValue::DShort(ShortValue(_)) => { tuple.0.push(CellValue::DShort(ShortValue(vs[idx].parse::<i16>().unwrap()))) }
                     CellValue::DUShort(UShortValue(_)) => { tuple.0.push(CellValue::DUShort(UShortValue(vs[idx].parse::<u16>().unwrap()))) }
                     CellValue::DString(StringValue(_)) => { tuple.0.push(CellValue::DString(StringValue(Rc::from(String::from(&vs[idx]))))) }
+                    CellValue::DEnum(EnumValue(ty, _, _)) => { tuple.0.push(CellValue::DEnum(EnumValue(ty.clone(), Rc::from(String::from(&vs[idx])), Rc::default()))) }
                     CellValue::DLString(LStringValue(_, _)) => {
                         let key = Rc::from(String::from(&vs[idx]));
                         if ls_data.contains_key(&key) {
@@ -1196,7 +1218,7 @@ fn collect_value(val: &str, dest: &mut CellValue, ls_map: &LSMap, ls_empty_map:
         CellValue::DShortList(ShortListValue(arr)) => {
             collect_vec_value(&mut arr.0, ls_map, &filter_val, ls_empty_map, pos);
         }
-        CellValue::DValueTuple(ValueTupleValue(arr)) => {
+        CellValue::DValueTuple(ValueTupleValue(arr)) | CellValue::DTuple(TupleValue(arr)) => {
             let vals = split_val(&filter_val[1..filter_val.len()-1]);
             for (idx, v) in arr.iter_mut().enumerate() {
                 match v {
@@ -1210,6 +1232,7 @@ fn collect_value(val: &str, dest: &mut CellValue, ls_map: &LSMap, ls_empty_map:
                     CellValue::DShort(ShortValue(ref mut v)) => { *v = vals[idx].parse::<i16>().unwrap(); }
                     CellValue::DUShort(UShortValue(ref mut v)) => { *v = vals[idx].parse::<u16>().unwrap(); }
                     CellValue::DString(StringValue(ref mut v)) => { *v = Rc::from(String::from(&vals[idx]));  }
+                    CellValue::DEnum(EnumValue(_, ref mut v, _)) => { *v = Rc::from(String::from(&vals[idx])); }
                     CellValue::DLString(LStringValue(ref mut k, ref mut v)) => {
                         let key = Rc::from(String::from(&vals[idx]));
                         if ls_data.contains_key(&key) {
@@ -1227,12 +1250,6 @@ fn collect_value(val: &str, dest: &mut CellValue, ls_map: &LSMap, ls_empty_map:
     }
 }
 
-pub trait ValueInfo {
-    // code
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()>;
-    // used by array/list code
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()>;
-}
 #[derive(Default)]
 pub struct EnumValue(pub Rc<String>, pub Rc<String>, pub Rc<String>); // (enum_name, val, base_name)
 
@@ -1297,444 +1314,60 @@ pub struct NoneValue(pub Rc<String>);
 #[derive(Default)]
 pub struct ErrorValue;
 
-//----------------------------------impl-------------------------------------------
-
-impl ValueInfo for ErrorValue {
-    fn value<W: Write + ?Sized>(&self, _stream: &mut W) -> Result<()> {
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, _stream: &mut W) -> Result<()> {
-        Ok(())
-    }
-}
-
-impl ValueInfo for NoneValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        match CellValue::get_type(&self.0) {
-            CellValue::DBool(_) => { stream.write("false".as_bytes())?; }
-            CellValue::DSByte(_) | CellValue::DLString(_) | CellValue::DInt(_) | CellValue::DShort(_) => { stream.write("-1".as_bytes())?; }
-            CellValue::DArray(_) | CellValue::DEnum(_) | CellValue::DList(_) | 
-            CellValue::DShortList(_) | CellValue::DTuple(_) | CellValue::DCustom(_) | CellValue::DString(_) => { stream.write("null".as_bytes())?; }
-            CellValue::DDouble(_) | CellValue::DFloat(_) => { stream.write("0.0".as_bytes())?; }
-            CellValue::DUInt(_) | CellValue::DByte(_) | CellValue::DUShort(_) => { stream.write("0".as_bytes())?; }
-            _ => {}
-        }
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("none".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for EnumValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write_fmt(format_args!("E{}{}.{}", self.2, self.0, self.1))?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, _stream: &mut W) -> Result<()> {
-        Ok(())
-    }
-}
-
-impl ValueInfo for ShortListValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("new ShortList(".as_bytes())?;
-        let mut cnt = 1;
-        for v in self.0.0.iter().skip(1) {
-            if let CellValue::DShort(vv) = v{
-                stream.write(vv.0.to_string().as_bytes())?;
-                if cnt < self.0.0.len()-1 {
-                    stream.write(",".as_bytes())?;
-                }
-            } else {
-                println!("ShortList value format failed");
-            }
-            cnt += 1;
-        }
-        stream.write(")".as_bytes())?;
-        Ok(())
-    }
 
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("ShortList".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for BoolValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        if self.0 == false {
-            stream.write("false".as_bytes())?;
-        } else {
-            stream.write("true".as_bytes())?;
-        }
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("bool".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for LStringValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write(self.1.to_string().as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("int".as_bytes())?;
-        Ok(())
-    }
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl ValueInfo for StringValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        if self.0.is_empty() {
-            stream.write("\"\"".as_bytes())?;
-        } else if self.0.as_str() == "\"\"" {
-            stream.write("\"\"".as_bytes())?;
-        } else if self.0.as_str().contains("\"") {
-            stream.write(self.0.as_bytes())?;
-        } else {
-            stream.write("\"".as_bytes())?;
-            stream.write(self.0.as_bytes())?;
-            stream.write("\"".as_bytes())?;
+    fn parse(val: &str, ty: &str) -> CellValue {
+        let kind: ENMap = Rc::default();
+        for (desc, ident) in [("火", "Fire"), ("水", "Water")] {
+            kind.borrow_mut().insert(Some(Rc::new(String::from(desc))), Some(Rc::new(String::from(ident))));
         }
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("string".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for ShortValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write(self.0.to_string().as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("short".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for UShortValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write(self.0.to_string().as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("ushort".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for IntValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write(self.0.to_string().as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("int".as_bytes())?;
-        Ok(())
+        let enmaps: Rc<RefCell<HashMap<String, ENMap>>> = Rc::default();
+        enmaps.borrow_mut().insert(String::from("Kind"), kind);
+        CellValue::new(&Rc::new(String::from(val)), &Rc::new(String::from(ty)), &Rc::default(), &std::collections::BTreeMap::default(), &Rc::default(), &enmaps, "Item", 0, 0)
     }
-}
-
-impl ValueInfo for UIntValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write(self.0.to_string().as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("uint".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for FloatValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write_fmt(format_args!("{:E}f", self.0))?;
-        //stream.write(self.0.to_string().as_bytes())?;
-        //stream.write("f".as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("float".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for DoubleValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write_fmt(format_args!("{:E}d", self.0))?;
-        //stream.write(self.0.to_string().as_bytes())?;
-        //stream.write("d".as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("double".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for ByteValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write(self.0.to_string().as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("byte".as_bytes())?;
-        Ok(())
-    }
-}
 
-impl ValueInfo for SByteValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write(self.0.to_string().as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("sbyte".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for CustomValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("new ".as_bytes())?;
-        stream.write(self.0.to_string().as_bytes())?;
-        stream.write("(".as_bytes())?;
-        for v in self.1.as_str()[1..self.1.len()-1].chars() {
-            match v {
-                '{' => { stream.write("new []{".as_bytes())?; },
-                _ => { stream.write(v.to_string().as_bytes())?; }
-            }
+    fn list(v: &CellValue) -> &Vec<CellValue> {
+        match v {
+            CellValue::DList(ListValue(data)) => data,
+            _ => panic!("not a list")
         }
-        stream.write(")".as_bytes())?;
-        Ok(())
     }
 
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write(self.0.as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for ArrayValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        if self.0.is_empty() {
-            stream.write("".as_bytes())?;
-        } else {
-            stream.write("new ".as_bytes())?;
-            self.ty(stream)?;
-            stream.write("{".as_bytes())?;
-            let mut cnt = 1;
-
-            for v in self.0.iter().skip(1) {
-                write_value_to_stream!(
-                    v,
-                    stream,
-                    CellValue::DBool,
-                    CellValue::DByte, 
-                    CellValue::DSByte, 
-                    CellValue::DInt, 
-                    CellValue::DUInt,
-                    CellValue::DFloat,
-                    CellValue::DDouble,
-                    CellValue::DShort, 
-                    CellValue::DUShort, 
-                    CellValue::DString,
-                    CellValue::DLString,
-                    CellValue::DCustom,
-                    CellValue::DTuple,
-                    CellValue::DValueTuple
-                );
-
-                if cnt < self.0.len()-1 {
-                    stream.write(",".as_bytes())?;
-                }
-                cnt += 1;
-            }
-            stream.write("}".as_bytes())?;
+    fn tuple(v: &CellValue) -> &Vec<CellValue> {
+        match v {
+            CellValue::DTuple(TupleValue(data)) => data,
+            _ => panic!("not a tuple")
         }
-        Ok(())
     }
 
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        (self.0)[0].get_basic_type_string(stream)?;
-        stream.write("[]".as_bytes())?;
-        Ok(())
-    }
-}
-
-impl ValueInfo for ListValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        if self.0.is_empty() {
-            stream.write("".as_bytes())?;
-        } else {
-            stream.write("new ".as_bytes())?;
-            self.ty(stream)?;
-            stream.write("{".as_bytes())?;
-            let mut cnt = 1;
-
-            for v in self.0.iter().skip(1) {
-                write_value_to_stream!(
-                    v,
-                    stream,
-                    CellValue::DBool,
-                    CellValue::DByte, 
-                    CellValue::DSByte, 
-                    CellValue::DInt, 
-                    CellValue::DUInt,
-                    CellValue::DFloat,
-                    CellValue::DDouble,
-                    CellValue::DShort, 
-                    CellValue::DUShort, 
-                    CellValue::DString,
-                    CellValue::DLString,
-                    CellValue::DCustom,
-                    CellValue::DShortList,
-                    CellValue::DTuple,
-                    CellValue::DValueTuple,
-                    CellValue::DArray,
-                    CellValue::DList
-                );
-
-                if cnt < self.0.len()-1 {
-                    stream.write(",".as_bytes())?;
-                }
-                cnt += 1;
-            }
-            
-            stream.write("}".as_bytes())?;
+    fn ident(v: &CellValue) -> (&str, &str, &str) {
+        match v {
+            CellValue::DEnum(EnumValue(name, ident, owner)) => (name.as_str(), ident.as_str(), owner.as_str()),
+            _ => panic!("not an enum")
         }
-        Ok(())
     }
 
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        stream.write("List<".as_bytes())?;
-        let first = self.0.first().unwrap();
-        match first {
-            CellValue::DArray(v) => { v.ty(stream)?; }
-            CellValue::DList(v) => { v.ty(stream)?; }
-            // basic type
-            _ => {
-                first.get_basic_type_string(stream)?;
-            }
-        }
-        stream.write(">".as_bytes())?;
-        Ok(())
-    }
-}
+    #[test]
+    fn enums_in_tuples_are_resolved() {
+        let v = parse("{1,水}", "Tuple<int,enum:Kind>");
+        assert!(!v.is_unresolved_enum());
+        assert_eq!(ident(&tuple(&v)[1]), ("Kind", "Water", "Item"));
+        assert!(parse("{1,Ice}", "Tuple<int,enum:Kind>").is_unresolved_enum());
 
-impl ValueInfo for TupleValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        let mut cnt = 0;
-        stream.write("new ".as_bytes())?;
-        self.ty(stream)?;
-        stream.write("(".as_bytes())?;
-        for v in self.0.iter() {
-            write_value_to_stream!(
-                v,
-                stream,
-                CellValue::DBool,
-                CellValue::DByte, 
-                CellValue::DSByte, 
-                CellValue::DInt, 
-                CellValue::DUInt,
-                CellValue::DFloat,
-                CellValue::DDouble,
-                CellValue::DShort, 
-                CellValue::DUShort, 
-                CellValue::DString,
-                CellValue::DLString,
-                CellValue::DCustom
-            );
-
-            if cnt < self.0.len()-1 {
-                stream.write(",".as_bytes())?;
-            }
-            cnt += 1;
-        }
-        stream.write(")".as_bytes())?;
-        Ok(())
+        let v = parse("{{1,Fire},{2,水}}", "List<Tuple<int,enum:Kind>>");
+        assert_eq!(list(&v).iter().skip(1).map(|v| ident(&tuple(v)[1]).1).collect::<Vec<_>>(), vec!["Fire", "Water"]);
+        assert!(parse("{{1,Fire},{2,Ice}}", "List<Tuple<int,enum:Kind>>").is_unresolved_enum());
     }
 
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        let mut cnt = 0;
-        stream.write("Tuple<".as_bytes())?;
-        for v in self.0.iter() {
-            v.get_basic_type_string(stream)?;
-            if cnt < self.0.len() - 1 {
-                stream.write(",".as_bytes())?;
-            }
-            cnt += 1;
-        }
-        stream.write(">".as_bytes())?;
-        Ok(())
+    #[test]
+    fn enums_in_nested_lists_are_resolved() {
+        let v = parse("{{Fire,水},{水}}", "List<List<enum:Kind>>");
+        assert!(!v.is_unresolved_enum());
+        let idents = list(&v).iter().skip(1).map(|v| list(v).iter().skip(1).map(|v| ident(v).1).collect::<Vec<_>>()).collect::<Vec<_>>();
+        assert_eq!(idents, vec![vec!["Fire", "Water"], vec!["Water"]]);
+        assert!(parse("{{Fire},{Ice}}", "List<List<enum:Kind>>").is_unresolved_enum());
     }
 }
-
-impl ValueInfo for ValueTupleValue {
-    fn value<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        let mut cnt = 0;
-        stream.write("new ".as_bytes())?;
-        self.ty(stream)?;
-        stream.write("(".as_bytes())?;
-        for v in self.0.iter() {
-            write_value_to_stream!(
-                v,
-                stream,
-                CellValue::DBool,
-                CellValue::DByte, 
-                CellValue::DSByte, 
-                CellValue::DInt, 
-                CellValue::DUInt,
-                CellValue::DFloat,
-                CellValue::DDouble,
-                CellValue::DShort, 
-                CellValue::DUShort, 
-                CellValue::DString,
-                CellValue::DLString,
-                CellValue::DCustom
-            );
-
-            if cnt < self.0.len()-1 {
-                stream.write(",".as_bytes())?;
-            }
-            cnt += 1;
-        }
-        stream.write(")".as_bytes())?;
-        Ok(())
-    }
-
-    fn ty<W: Write + ?Sized>(&self, stream: &mut W) -> Result<()> {
-        let mut cnt = 0;
-        stream.write("ValueTuple<".as_bytes())?;
-        for v in self.0.iter() {
-            v.get_basic_type_string(stream)?;
-            if cnt < self.0.len() - 1 {
-                stream.write(",".as_bytes())?;
-            }
-            cnt += 1;
-        }
-        stream.write(">".as_bytes())?;
-        Ok(())
-    }
-}
\ No newline at end of file
@@ -1,5 +1,5 @@
-use std::collections::HashMap;
-use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 use crate::RDM;
@@ -15,7 +15,9 @@ pub type RawValData<'a> = (usize, (&'a str, Vec<&'a str>, CellValue));
 pub struct FKValue<'a> {
     rawdata: HashMap<usize, ColRawData<'a>>, // <col, data>
     fk_map: RefCell<FKMap>,
-    outvals: RefCell<HashMap<usize, Vec<String>>>
+    outvals: RefCell<HashMap<usize, Vec<String>>>,
+    targets: RefCell<HashMap<usize, BTreeSet<String>>>, // <col, tables the values refer to>
+    cur_col: Cell<usize>,
 }
 
 struct ColRawData<'a> {
@@ -41,7 +43,7 @@ impl<'a> FKValue<'a> {
             }
         }
 
-        Self { rawdata, fk_map, outvals }
+        Self { rawdata, fk_map, outvals, targets: RefCell::default(), cur_col: Cell::default() }
     }
 
     pub fn parse(&'a self) {
@@ -65,8 +67,14 @@ impl<'a> FKValue<'a> {
         }
     }
 
+    // tables the column refers to, the dynamic ones only when some value names them
+    pub fn get_targets(&self, col: usize) -> Vec<String> {
+        self.targets.borrow().get(&col).map(|v| v.iter().cloned().collect()).unwrap_or_default()
+    }
+
     //----------------------------private-------------------------------
     fn parse_internal(&'a self, val: &'a str, pattern: &'a str, col: &'a usize, ty: &'a CellValue) {
+        self.cur_col.set(*col);
         let rval = val.chars().filter(|c| *c != ' ').collect::<String>();
         // new value
         let mut rs = String::default();
@@ -75,9 +83,7 @@ impl<'a> FKValue<'a> {
             let mut ch_stack = Stack::<char>::new();
             let base_name = pattern.chars().filter(|c| *c != '{' && *c != '}').collect::<String>();
 
-            if !self.fk_map.borrow().contains_key(&base_name) {
-                self.read_fk_table(base_name.clone());
-            }
+            self.use_fk_table(&base_name);
             if let Some(fks) = self.fk_map.borrow().get(&base_name) {
                 for v in rval.chars() {
                     match v {
@@ -112,6 +118,14 @@ impl<'a> FKValue<'a> {
         nvals.push(rs);
     }
     
+    // records the edge of the current column, reads the table at the first use
+    fn use_fk_table(&self, name: &str) {
+        self.targets.borrow_mut().entry(self.cur_col.get()).or_default().insert(String::from(name));
+        if !self.fk_map.borrow().contains_key(name) {
+            self.read_fk_table(String::from(name));
+        }
+    }
+
     fn read_fk_table(&self, name: String) {
         let mut file_name = String::from(&name);
         file_name.push('.');
@@ -177,8 +191,8 @@ impl<'a> FKValue<'a> {
                                         }
                                     }
 
-                                    if !vals[cnt].is_empty() && !self.fk_map.borrow().contains_key(&vals[cnt][1..vals[cnt].len()-1]) {
-                                        self.read_fk_table(String::from(&vals[cnt][1..vals[cnt].len()-1]));
+                                    if !vals[cnt].is_empty() {
+                                        self.use_fk_table(&vals[cnt][1..vals[cnt].len()-1]);
                                     }
                                 }
                                 cnt += 1;
@@ -319,8 +333,8 @@ impl<'a> FKValue<'a> {
                     let mut cnt = 0;
             
                     for v in indexs.iter() {
-                        if !v.is_empty() && !self.fk_map.borrow().contains_key(*v) {
-                            self.read_fk_table(String::from(*v));
+                        if !v.is_empty() {
+                            self.use_fk_table(v);
                         }
                     }
 
@@ -358,8 +372,8 @@ impl<'a> FKValue<'a> {
                             tmp = &v;
                         }
 
-                        if !tmp.is_empty() && !self.fk_map.borrow().contains_key(tmp) {
-                            self.read_fk_table(String::from(tmp));
+                        if !tmp.is_empty() {
+                            self.use_fk_table(tmp);
                         }
                     }
 
@@ -415,8 +429,8 @@ impl<'a> FKValue<'a> {
                     if is_arr { dest.push('{'); }
 
                     for v in indexs.iter() {
-                        if !v.is_empty() && !self.fk_map.borrow().contains_key(*v) && !v.starts_with('{') {
-                            self.read_fk_table(String::from(*v));
+                        if !v.is_empty() && !v.starts_with('{') {
+                            self.use_fk_table(v);
                         }
                     }
 
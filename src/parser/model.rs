@@ -0,0 +1,118 @@
+use crate::defs::ItemStr;
+use crate::reference::RefData;
+
+use super::{CellValue, KeyType, EnumMember};
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+use std::sync::Arc;
+
+// one column of the Template sheet, skip columns('#' or no identifier) are not included
+pub struct Column {
+    pub comment: ItemStr,
+    pub ident: Rc<String>,
+    pub ty: Rc<String>,                     // length of fixed arrays removed, int[3] -> int[]
+    pub fixed_len: Option<usize>,           // the removed length
+    pub fk: ItemStr,                        // pattern of the foreign key row, without the leading '*'
+    pub fk_targets: Vec<String>,            // tables the values were looked up in, sorted
+    pub default: Option<Box<CellValue>>,    // None if the default cell is empty or None
+    pub values: Vec<Box<CellValue>>,        // one per data row, FKs, enums and LStrings resolved
+}
+
+// enum of a t_ sheet, the enum library or the enumflags groups
+pub struct EnumDef {
+    pub owner: String,  // base name of the workbook, empty for the shared ones
+    pub name: String,
+    pub members: Vec<EnumMember>,
+    pub reserved: BTreeSet<i32>,
+}
+
+// everything parsed from one workbook, consumed by the emitters
+pub struct TableModel {
+    pub name: String,
+    pub columns: Vec<Column>,
+    pub ref_names: Vec<Rc<String>>,         // #RefName of every data row
+    pub lines: usize,
+    pub id_type: Rc<String>,
+    pub key_type: KeyType,
+    pub refdata: Option<Arc<RefData>>,
+    pub enums: Vec<EnumDef>,
+    pub enum_refs: Vec<EnumDef>,            // enums of the library and other workbooks used by the columns
+    pub enumflags: HashMap<String, Vec<Rc<String>>>,
+}
+
+impl Default for TableModel {
+    fn default() -> Self {
+        TableModel {
+            name: String::default(),
+            columns: Vec::default(),
+            ref_names: Vec::default(),
+            lines: 0,
+            id_type: Rc::default(),
+            key_type: KeyType::None,
+            refdata: None,
+            enums: Vec::default(),
+            enum_refs: Vec::default(),
+            enumflags: HashMap::default(),
+        }
+    }
+}
+
+impl Column {
+    // column of the tests, without comment, FK or default
+    #[cfg(test)]
+    pub fn new(ident: &str, ty: &str, values: Vec<CellValue>) -> Self {
+        Column {
+            comment: None,
+            ident: Rc::new(String::from(ident)),
+            ty: Rc::new(String::from(ty)),
+            fixed_len: None,
+            fk: None,
+            fk_targets: Vec::default(),
+            default: None,
+            values: values.into_iter().map(Box::new).collect(),
+        }
+    }
+
+    // empty cells of the row fall back to the default
+    pub fn value(&self, row: usize) -> &CellValue {
+        match &self.default {
+            Some(default) if self.values[row].is_none() => default,
+            _ => &self.values[row],
+        }
+    }
+
+    // *Table or *{Table}: the values are rows of the target
+    pub fn fk_table(&self) -> Option<&str> {
+        self.fk.as_ref().map(|v| v.trim_matches(|c| c == '{' || c == '}'))
+            .filter(|v| !v.is_empty() && v.chars().all(|c| c.is_alphanumeric()))
+    }
+}
+
+impl TableModel {
+    // value of the member ident of E{owner}{name}, from the workbook itself, the library or another workbook
+    pub fn enum_value(&self, owner: &str, name: &str, ident: &str) -> Option<i32> {
+        self.enums.iter().chain(self.enum_refs.iter())
+            .find(|v| v.owner == owner && v.name == name)
+            .and_then(|v| v.members.iter().find(|m| m.0.as_str() == ident))
+            .map(|v| v.1)
+    }
+
+    // (DefKey, TemplateId), ids come from the ref mapping when the workbook has one
+    pub fn def_keys(&self) -> Vec<(Rc<String>, i32)> {
+        let mut ret = Vec::default();
+        if let KeyType::DefKey(ref vals) = self.key_type {
+            for (key, row, ref_name) in vals.iter() {
+                if let (Some(key), Some(ref_name)) = (key, ref_name) {
+                    if key.is_empty() {
+                        continue;
+                    }
+                    match self.refdata.as_ref().and_then(|v| v.data.get(ref_name.as_str())) {
+                        Some(id) => ret.push((key.clone(), *id)),
+                        None => ret.push((key.clone(), *row as i32)),
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
@@ -0,0 +1,22 @@
+mod config;
+
+use config::e_item_kind::EItemKind;
+
+fn main() {
+    let rows = config::item::rows();
+    assert_eq!(rows.len(), 2);
+
+    assert_eq!(rows[0].id, 1);
+    assert_eq!(rows[0].name.as_deref(), Some("sword"));
+    assert_eq!(rows[0].kind, Some(EItemKind::Weapon));
+    assert_eq!(rows[0].r#type, 3);
+    assert_eq!(rows[0].costs, Some(vec![1, -2]));
+    assert_eq!(rows[0].range, Some((2, 0.5)));
+
+    assert_eq!(rows[1].name, None);
+    assert_eq!(rows[1].kind, None);
+    assert_eq!(rows[1].r#type, -1);
+    assert_eq!(rows[1].costs, Some(vec![]));
+    assert_eq!(rows[1].range, Some((0, f32::INFINITY)));
+    assert_eq!(EItemKind::try_from(200), Ok(EItemKind::Weapon));
+}